@@ -1068,6 +1068,35 @@ pub enum Expr {
         subquery: Box<Query>,
         negated: bool,
     },
+    /// A Cypher existential subquery `[ NOT ] EXISTS { MATCH <pattern> [ WHERE <expr> ] }`
+    CypherExists {
+        pattern: String,
+        where_clause: Option<Box<Expr>>,
+        negated: bool,
+    },
+    /// A Cypher path pattern used as an expression, e.g. the argument of
+    /// `size((n)-[:KNOWS]->())`
+    CypherPattern(String),
+    /// A Cypher query used as an `IN` subquery, e.g.
+    /// `[ NOT ] IN (MATCH <pattern> [ WHERE <expr> ] RETURN <expr>)`
+    CypherInSubquery {
+        expr: Box<Expr>,
+        query: Box<Statement>,
+        negated: bool,
+    },
+    /// A Cypher test for membership of a list parameter, e.g.
+    /// `<expr> [ NOT ] IN $ids`
+    CypherInParameter {
+        expr: Box<Expr>,
+        parameter: Box<Expr>,
+        negated: bool,
+    },
+    /// A Cypher string predicate, e.g. `<expr> STARTS WITH <expr>`
+    CypherStringMatch {
+        expr: Box<Expr>,
+        operator: CypherStringOperator,
+        pattern: Box<Expr>,
+    },
     /// A parenthesized subquery `(SELECT ...)`, used in expression like
     /// `SELECT (subquery) AS x` or `WHERE (subquery) = x`
     Subquery(Box<Query>),
@@ -1795,6 +1824,45 @@ impl fmt::Display for Expr {
                 if *negated { "NOT " } else { "" },
                 subquery
             ),
+            Expr::CypherExists {
+                pattern,
+                where_clause,
+                negated,
+            } => {
+                write!(
+                    f,
+                    "{}EXISTS {{ MATCH {pattern}",
+                    if *negated { "NOT " } else { "" }
+                )?;
+                if let Some(where_clause) = where_clause {
+                    write!(f, " WHERE {where_clause}")?;
+                }
+                write!(f, " }}")
+            }
+            Expr::CypherPattern(pattern) => f.write_str(pattern),
+            Expr::CypherInSubquery {
+                expr,
+                query,
+                negated,
+            } => write!(
+                f,
+                "{expr} {}IN ({query})",
+                if *negated { "NOT " } else { "" }
+            ),
+            Expr::CypherInParameter {
+                expr,
+                parameter,
+                negated,
+            } => write!(
+                f,
+                "{expr} {}IN {parameter}",
+                if *negated { "NOT " } else { "" }
+            ),
+            Expr::CypherStringMatch {
+                expr,
+                operator,
+                pattern,
+            } => write!(f, "{expr} {operator} {pattern}"),
             Expr::Subquery(s) => write!(f, "({s})"),
             Expr::GroupingSets(sets) => {
                 write!(f, "GROUPING SETS (")?;
@@ -3288,12 +3356,70 @@ pub enum Statement {
     /// ```cypher
     /// Basic Cypher graph query
     CypherQuery {
+        /// The `MATCH` pattern, empty for a bare `RETURN`
         pattern: String,
         where_clause: Option<Expr>,
+        /// `RETURN DISTINCT`
+        distinct: bool,
         return_items: Vec<SelectItem>,
+        order_by: Vec<OrderByExpr>,
+        /// `SKIP <expr>`
+        skip: Option<Expr>,
+        /// `LIMIT <expr>`
+        limit: Option<Expr>,
     },
     CypherCreate {
         pattern: String,
+        /// Variables passed on by `CREATE ... WITH n MATCH ...`, empty for
+        /// `WITH *`
+        with: Vec<Ident>,
+        /// The `MATCH` query following the `WITH`, if any
+        query: Option<Box<Statement>>,
+    },
+    /// Cypher `MATCH <pattern> [WHERE <expr>] [DETACH] DELETE <variables>`
+    CypherDelete {
+        pattern: String,
+        where_clause: Option<Expr>,
+        /// `DETACH DELETE`: also delete the relationships of deleted nodes
+        detach: bool,
+        /// Deleted variables; empty for `DELETE *`
+        variables: Vec<Ident>,
+        /// Items of a `RETURN` following the `DELETE`, if any
+        return_items: Vec<SelectItem>,
+    },
+    /// Cypher `<query> UNION [ALL] <query>`, where the left query may itself
+    /// be a union
+    CypherUnion {
+        left: Box<Statement>,
+        right: Box<Statement>,
+        all: bool,
+    },
+    /// Cypher `MATCH <pattern> [WHERE <expr>] REMOVE <items>`
+    CypherRemove {
+        pattern: String,
+        where_clause: Option<Expr>,
+        items: Vec<CypherRemoveItem>,
+    },
+    /// Cypher `MATCH <pattern> [WHERE <expr>] SET <items>`
+    CypherSet {
+        pattern: String,
+        where_clause: Option<Expr>,
+        items: Vec<CypherSetItem>,
+    },
+    /// Cypher `MATCH <pattern> [WHERE <expr>] MERGE <relationship>`, creating
+    /// the relationship between matched nodes unless it already exists
+    CypherMerge {
+        pattern: String,
+        where_clause: Option<Expr>,
+        /// The merged pattern, such as `(a)-[:KNOWS]->(b)`
+        merge: String,
+    },
+    /// Cypher `CALL { <subquery> } RETURN <items>`, returning from the rows
+    /// of an uncorrelated subquery
+    CypherCall {
+        subquery: Box<Statement>,
+        /// The `RETURN` following the subquery, as a query without a pattern
+        query: Box<Statement>,
     },
     /// ```sql
     /// CREATE VIRTUAL TABLE .. USING <module_name> (<module_args>)`
@@ -4766,23 +4892,119 @@ impl fmt::Display for Statement {
             Statement::CypherQuery {
                 pattern,
                 where_clause,
+                distinct,
                 return_items,
+                order_by,
+                skip,
+                limit,
             } => {
-                write!(f, "MATCH {}", pattern)?;
+                if !pattern.is_empty() {
+                    write!(f, "MATCH {} ", pattern)?;
+                }
 
                 if let Some(ref where_expr) = where_clause {
-                    write!(f, " WHERE {}", where_expr)?;
+                    write!(f, "WHERE {} ", where_expr)?;
                 }
 
-                write!(f, " RETURN ")?;
+                write!(f, "RETURN ")?;
+                if *distinct {
+                    write!(f, "DISTINCT ")?;
+                }
                 write!(f, "{}", display_comma_separated(return_items))?;
+
+                if !order_by.is_empty() {
+                    write!(f, " ORDER BY {}", display_comma_separated(order_by))?;
+                }
+                if let Some(skip) = skip {
+                    write!(f, " SKIP {skip}")?;
+                }
+                if let Some(limit) = limit {
+                    write!(f, " LIMIT {limit}")?;
+                }
                 Ok(())
             },
             Statement::CypherCreate {
-                pattern
+                pattern,
+                with,
+                query,
             } => {
-                write!(f, "CREATE {}", pattern)
+                write!(f, "CREATE {}", pattern)?;
+                if let Some(query) = query {
+                    if with.is_empty() {
+                        write!(f, " WITH * {}", query)?;
+                    } else {
+                        write!(f, " WITH {} {}", display_comma_separated(with), query)?;
+                    }
+                }
+                Ok(())
             },
+            Statement::CypherDelete {
+                pattern,
+                where_clause,
+                detach,
+                variables,
+                return_items,
+            } => {
+                write!(f, "MATCH {}", pattern)?;
+                if let Some(ref where_expr) = where_clause {
+                    write!(f, " WHERE {}", where_expr)?;
+                }
+                if *detach {
+                    write!(f, " DETACH")?;
+                }
+                if variables.is_empty() {
+                    write!(f, " DELETE *")?;
+                } else {
+                    write!(f, " DELETE {}", display_comma_separated(variables))?;
+                }
+                if !return_items.is_empty() {
+                    write!(f, " RETURN {}", display_comma_separated(return_items))?;
+                }
+                Ok(())
+            },
+            Statement::CypherUnion { left, right, all } => {
+                write!(f, "{left} UNION ")?;
+                if *all {
+                    write!(f, "ALL ")?;
+                }
+                write!(f, "{right}")
+            }
+            Statement::CypherRemove {
+                pattern,
+                where_clause,
+                items,
+            } => {
+                write!(f, "MATCH {}", pattern)?;
+                if let Some(ref where_expr) = where_clause {
+                    write!(f, " WHERE {}", where_expr)?;
+                }
+                write!(f, " REMOVE {}", display_comma_separated(items))
+            }
+            Statement::CypherSet {
+                pattern,
+                where_clause,
+                items,
+            } => {
+                write!(f, "MATCH {}", pattern)?;
+                if let Some(ref where_expr) = where_clause {
+                    write!(f, " WHERE {}", where_expr)?;
+                }
+                write!(f, " SET {}", display_comma_separated(items))
+            }
+            Statement::CypherMerge {
+                pattern,
+                where_clause,
+                merge,
+            } => {
+                write!(f, "MATCH {}", pattern)?;
+                if let Some(ref where_expr) = where_clause {
+                    write!(f, " WHERE {}", where_expr)?;
+                }
+                write!(f, " MERGE {}", merge)
+            }
+            Statement::CypherCall { subquery, query } => {
+                write!(f, "CALL {{ {subquery} }} {query}")
+            }
             Statement::LoadData {
                 local,
                 inpath,
@@ -10034,6 +10256,80 @@ pub enum ReturnStatementValue {
     Expr(Expr),
 }
 
+/// An item of a Cypher `REMOVE` clause
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub enum CypherRemoveItem {
+    /// `n.property`
+    Property { variable: Ident, property: Ident },
+    /// `n:Label`
+    Label { variable: Ident, label: Ident },
+}
+
+impl fmt::Display for CypherRemoveItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CypherRemoveItem::Property { variable, property } => write!(f, "{variable}.{property}"),
+            CypherRemoveItem::Label { variable, label } => write!(f, "{variable}:{label}"),
+        }
+    }
+}
+
+/// An item of a Cypher `SET` clause
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub enum CypherSetItem {
+    /// `n.property = value`
+    Property {
+        variable: Ident,
+        property: Ident,
+        value: Expr,
+    },
+    /// `n += {key: value, ...}`, setting each property of the map
+    Merge { variable: Ident, properties: Expr },
+}
+
+impl fmt::Display for CypherSetItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CypherSetItem::Property {
+                variable,
+                property,
+                value,
+            } => write!(f, "{variable}.{property} = {value}"),
+            CypherSetItem::Merge {
+                variable,
+                properties,
+            } => write!(f, "{variable} += {properties}"),
+        }
+    }
+}
+
+/// Operator of a Cypher string predicate
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub enum CypherStringOperator {
+    /// `STARTS WITH`
+    StartsWith,
+    /// `ENDS WITH`
+    EndsWith,
+    /// `CONTAINS`
+    Contains,
+}
+
+impl fmt::Display for CypherStringOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            CypherStringOperator::StartsWith => "STARTS WITH",
+            CypherStringOperator::EndsWith => "ENDS WITH",
+            CypherStringOperator::Contains => "CONTAINS",
+        })
+    }
+}
+
 /// Represents an `OPEN` statement.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -352,6 +352,12 @@ impl Spanned for Statement {
             Statement::CreateTable(create_table) => create_table.span(),
             Statement::CypherQuery{ .. } => Span::empty(),
             Statement::CypherCreate{ .. } => Span::empty(),
+            Statement::CypherDelete{ .. } => Span::empty(),
+            Statement::CypherUnion{ .. } => Span::empty(),
+            Statement::CypherRemove{ .. } => Span::empty(),
+            Statement::CypherSet{ .. } => Span::empty(),
+            Statement::CypherMerge{ .. } => Span::empty(),
+            Statement::CypherCall{ .. } => Span::empty(),
             Statement::CreateVirtualTable {
                 name,
                 if_not_exists: _,
@@ -1521,6 +1527,11 @@ impl Spanned for Expr {
                     .chain(iter::once(end_token.0.span)),
             ),
             Expr::Exists { subquery, .. } => subquery.span(),
+            Expr::CypherExists { .. } => Span::empty(),
+            Expr::CypherPattern(_) => Span::empty(),
+            Expr::CypherInSubquery { .. } => Span::empty(),
+            Expr::CypherInParameter { .. } => Span::empty(),
+            Expr::CypherStringMatch { .. } => Span::empty(),
             Expr::Subquery(query) => query.span(),
             Expr::Struct { .. } => Span::empty(),
             Expr::Named { .. } => Span::empty(),
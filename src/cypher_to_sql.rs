@@ -1,328 +1,7590 @@
-use crate::ast::*;
+use core::fmt;
+use std::collections::HashMap;
+
 use crate::ast::helpers::attached_token::AttachedToken;
+use crate::ast::helpers::stmt_create_table::CreateTableBuilder;
+use crate::ast::*;
+use crate::dialect::GenericDialect;
+use crate::keywords::{Keyword, RESERVED_FOR_COLUMN_ALIAS};
+use crate::parser::{Parser, ParserError};
+use crate::tokenizer::{Location, Token, Tokenizer};
+
+/// Column holding the identity of a node row.
+const NODE_ID_COLUMN: &str = "id";
+/// Column of an edge row referencing the node the relationship starts from.
+const EDGE_SOURCE_COLUMN: &str = "source_id";
+/// Column of an edge row referencing the node the relationship points to.
+const EDGE_TARGET_COLUMN: &str = "target_id";
+/// Temporary table holding the ids of the nodes a `DELETE` deletes.
+const DELETED_NODES_TABLE: &str = "_deleted";
+
+/// Options controlling how Cypher statements are converted to SQL.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConversionOptions {
+    /// Edge table used for relationships that do not name a type, such as
+    /// `(a)-[]->(b)`. When unset, such relationships are rejected.
+    pub default_edge_table: Option<String>,
+    /// Node table used for nodes that do not name a label, such as
+    /// `MATCH (n)`. When unset, such nodes are rejected.
+    pub default_label: Option<String>,
+    /// Convert `CREATE (n:Label $props)` to `INSERT INTO Label VALUES ($props)`,
+    /// leaving the caller to bind a whole row. When unset, such statements
+    /// are rejected since their columns cannot be known statically.
+    pub parameter_map_placeholder: bool,
+    /// Emit `=` comparisons as `IS NOT DISTINCT FROM`, so that comparing
+    /// two nulls matches instead of yielding null.
+    pub null_safe_equality: bool,
+    /// Emit `+` as string concatenation, for queries where Cypher's `+`
+    /// joins strings rather than adding numbers.
+    pub string_concat_plus: bool,
+    /// Declared SQL types of node properties, keyed by `(label, property)`.
+    /// Values inserted into a declared column are wrapped in a `CAST`.
+    pub column_types: HashMap<(String, String), DataType>,
+    /// How `CREATE` builds the row of the generated `INSERT`.
+    pub insert_style: InsertStyle,
+    /// Edge tables cleared of the relationships of nodes removed by
+    /// `DETACH DELETE`, in addition to those named in its pattern.
+    pub edge_tables: Vec<String>,
+    /// SQL dialect the generated statements are meant to run on.
+    pub target_dialect: TargetDialect,
+    /// How the predicates of inline property maps, such as the `name` in
+    /// `(n:Person {name: 'Alice'})`, combine with the `WHERE` clause.
+    pub property_predicate_combinator: PredicateCombinator,
+    /// Insert map property values, such as `{a: 1, b: {c: 2}}`, as JSON
+    /// literals, and build maps of other values, such as
+    /// `RETURN {name: n.name}`, as JSON objects. When unset, such values
+    /// are rejected.
+    pub map_as_json: bool,
+    /// Render SQL text across several lines, one clause per line, instead
+    /// of on a single line.
+    pub pretty: bool,
+    /// Read inline property values of the form `{age: {$gt: 18}}` as
+    /// comparisons, for the operators `$gt`, `$gte`, `$lt`, `$lte` and `$ne`.
+    pub operator_maps: bool,
+    /// Convert `RETURN n` of a single node to `n.*` rather than `*`, which
+    /// stays unambiguous when the pattern joins several tables.
+    pub qualified_wildcard: bool,
+    /// Where `ORDER BY` sorts nulls when an item doesn't say, as in
+    /// `ORDER BY n.age NULLS LAST`. When unset, the database default applies.
+    pub nulls_ordering: Option<NullsOrdering>,
+    /// Column lists given to the alias of each table, keyed by table name,
+    /// as in `FROM Person AS n (id, name)`. The names rename the table's
+    /// columns in order, for tables whose columns aren't named after the
+    /// properties they hold.
+    pub table_alias_columns: HashMap<String, Vec<String>>,
+    /// Compare bare properties tested in `WHERE`, as in `WHERE n.active`,
+    /// to `true`, for databases without boolean column predicates.
+    pub explicit_bool: bool,
+    /// Render `MATCH` queries in pipe syntax, as in
+    /// `FROM Person AS n |> WHERE n.age > 18 |> SELECT n.name`, for
+    /// databases reading it such as DataFusion and BigQuery. Queries that
+    /// group, deduplicate or skip rows without a limit keep a plain `SELECT`.
+    pub emit_pipe: bool,
+    /// Convert `RETURN DISTINCT` to PostgreSQL's `DISTINCT ON` the first
+    /// returned item, keeping one row per value of it, when targeting
+    /// [`TargetDialect::PostgreSql`]. A query whose `ORDER BY` starts with
+    /// another item, which `DISTINCT ON` rejects, keeps a plain `DISTINCT`.
+    pub distinct_on: bool,
+    /// Read inline list values of two items, as in `{age: [18, 65]}`, as
+    /// inclusive ranges, `age >= 18 AND age <= 65`, rather than as the
+    /// values allowed by an `IN`. Cypher itself only has the `IN` reading,
+    /// so lists of any other length are rejected when set.
+    pub range_maps: bool,
+    /// Tables backing node labels, keyed by label, for labels not stored in
+    /// a table of their own name. A dotted name such as `analytics.persons`
+    /// names a table in another schema.
+    pub label_tables: HashMap<String, String>,
+    /// Quote every table, alias and column identifier with ANSI double
+    /// quotes, as in `SELECT "n"."name" FROM "Person" AS "n"`, whatever the
+    /// target dialect, for output portable across databases. This includes
+    /// the reserved words otherwise quoted with MySQL's backticks. Function
+    /// names are left unquoted, since quoting them makes their case
+    /// significant.
+    pub ansi_quoting: bool,
+    /// Convert Cypher's spatial functions to their PostGIS counterparts:
+    /// `point({x: 1, y: 2})`, or with `longitude` and `latitude`, to
+    /// `ST_MakePoint(1, 2)` and `distance(a, b)` or `point.distance(a, b)`
+    /// to `ST_Distance(a, b)`. When unset, they are passed through as is.
+    pub spatial: bool,
+    /// How `RETURN p` converts a named path `p = (a)-[:KNOWS]->(b)`, which
+    /// SQL has no type for.
+    pub path_return: PathReturn,
+}
+
+/// Position of nulls in a sort order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NullsOrdering {
+    /// `NULLS FIRST`
+    First,
+    /// `NULLS LAST`
+    Last,
+}
+
+/// Operator joining two predicates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PredicateCombinator {
+    /// `a AND b`
+    #[default]
+    And,
+    /// `a OR b`
+    Or,
+}
+
+/// How a converted `CREATE` supplies the row it inserts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InsertStyle {
+    /// `INSERT INTO Person (name) VALUES ('Alice')`
+    #[default]
+    Values,
+    /// `INSERT INTO Person (name) SELECT 'Alice' AS name`
+    Select,
+}
+
+/// How a named path, such as `p` in `MATCH p = (a)-->(b) RETURN p`, is
+/// returned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PathReturn {
+    /// Reject the statement.
+    #[default]
+    Error,
+    /// The ids of the path's first and last node: `a.id || '->' || b.id`
+    EndpointIds,
+}
+
+/// SQL dialect targeted by the conversion, where dialects differ in how
+/// they spell an equivalent construct.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TargetDialect {
+    /// Standard SQL, e.g. `CAST(x AS VARCHAR)`
+    #[default]
+    Generic,
+    /// PostgreSQL, e.g. `CAST(x AS TEXT)`
+    PostgreSql,
+    /// MySQL, e.g. `CAST(x AS CHAR)`
+    MySql,
+}
+
+impl TargetDialect {
+    /// Type that `toString` casts its argument to.
+    fn string_type(self) -> DataType {
+        match self {
+            TargetDialect::Generic => DataType::Varchar(None),
+            TargetDialect::PostgreSql => DataType::Text,
+            TargetDialect::MySql => DataType::Char(None),
+        }
+    }
+
+    /// Whether list values are written `ARRAY['a', 'b']` rather than
+    /// `['a', 'b']`.
+    fn array_keyword(self) -> bool {
+        matches!(self, TargetDialect::PostgreSql)
+    }
+
+    /// Concatenates two strings: `a || b`, or `CONCAT(a, b)` on MySQL where
+    /// `||` means `OR`.
+    fn concat(self, left: Expr, right: Expr) -> Expr {
+        match self {
+            TargetDialect::MySql => function("CONCAT", vec![left, right]),
+            _ => binary_op(left, BinaryOperator::StringConcat, right),
+        }
+    }
+
+    /// Casts JSON text to the JSON type: `CAST('{}' AS JSON)`, or
+    /// `'{}'::JSON` on PostgreSQL.
+    fn json(self, text: String) -> Expr {
+        Expr::Cast {
+            kind: match self {
+                TargetDialect::PostgreSql => CastKind::DoubleColon,
+                _ => CastKind::Cast,
+            },
+            expr: Box::new(Expr::Value(Value::SingleQuotedString(text).into())),
+            data_type: DataType::JSON,
+            format: None,
+        }
+    }
+
+    /// Builds a JSON object of computed values:
+    /// `json_build_object('a', x, 'b', y)`, or `JSON_OBJECT(...)` on MySQL.
+    fn json_object(self, fields: Vec<(String, Expr)>) -> Expr {
+        let name = match self {
+            TargetDialect::MySql => "JSON_OBJECT",
+            _ => "json_build_object",
+        };
+        let args = fields
+            .into_iter()
+            .flat_map(|(key, value)| [Expr::Value(Value::SingleQuotedString(key).into()), value])
+            .collect();
+        function(name, args)
+    }
+
+    /// Function counting the characters of a string: `LENGTH`, or
+    /// `CHAR_LENGTH` on MySQL, whose `LENGTH` counts bytes.
+    fn string_length_function(self) -> &'static str {
+        match self {
+            TargetDialect::MySql => "CHAR_LENGTH",
+            _ => "LENGTH",
+        }
+    }
+
+    /// Tables to select constants from: none, or MySQL's `DUAL`, since
+    /// older MySQL versions reject a `SELECT` without `FROM`.
+    fn constant_from(self) -> Vec<TableWithJoins> {
+        match self {
+            TargetDialect::MySql => vec![TableWithJoins {
+                relation: table_factor("DUAL".to_string(), None),
+                joins: vec![],
+            }],
+            _ => vec![],
+        }
+    }
+
+    /// Tests whether `expr` is an item of the list bound to `parameter`: with
+    /// `= ANY($ids)` on PostgreSQL, which binds arrays, and elsewhere with
+    /// `IN ($ids)`, for drivers expanding a list into several parameters.
+    fn in_parameter(self, expr: Expr, parameter: Expr, negated: bool) -> Expr {
+        match (self, negated) {
+            (TargetDialect::PostgreSql, false) => Expr::AnyOp {
+                left: Box::new(expr),
+                compare_op: BinaryOperator::Eq,
+                right: Box::new(parameter),
+                is_some: false,
+            },
+            (TargetDialect::PostgreSql, true) => Expr::AllOp {
+                left: Box::new(expr),
+                compare_op: BinaryOperator::NotEq,
+                right: Box::new(parameter),
+            },
+            _ => Expr::InList {
+                expr: Box::new(expr),
+                list: vec![parameter],
+                negated,
+            },
+        }
+    }
+
+    /// Sorts by `order_by`, placing nulls first or last as it asks: with
+    /// `NULLS FIRST` or `NULLS LAST`, or on MySQL, which lacks them, with a
+    /// leading `x IS NULL` sort key.
+    fn order_by_nulls(self, mut order_by: OrderByExpr) -> Vec<OrderByExpr> {
+        match (self, order_by.options.nulls_first) {
+            (TargetDialect::MySql, Some(nulls_first)) => {
+                order_by.options.nulls_first = None;
+                let is_null = OrderByExpr {
+                    expr: Expr::IsNull(Box::new(order_by.expr.clone())),
+                    options: OrderByOptions {
+                        asc: Some(!nulls_first),
+                        nulls_first: None,
+                    },
+                    with_fill: None,
+                };
+                vec![is_null, order_by]
+            }
+            _ => vec![order_by],
+        }
+    }
+
+    /// Column identifier for `name`, quoted when it is a reserved keyword
+    /// such as `order` or `group`, or not a plain identifier, such as the
+    /// `first name` of `{"first name": 'Al'}`.
+    fn column_ident(self, name: &str) -> Ident {
+        let plain = name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        let reserved = !plain
+            || matches!(
+                Token::make_word(name, None),
+                Token::Word(word) if RESERVED_FOR_COLUMN_ALIAS.contains(&word.keyword)
+            );
+        match (reserved, self) {
+            (false, _) => Ident::new(name),
+            (true, TargetDialect::MySql) => Ident::with_quote('`', name),
+            (true, _) => Ident::with_quote('"', name),
+        }
+    }
+}
+
+/// Error produced when a Cypher statement cannot be converted to SQL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CypherConversionError {
+    /// The pattern text is malformed.
+    InvalidPattern {
+        message: String,
+        /// Character offset into the pattern text where the problem was
+        /// found, when known.
+        position: Option<usize>,
+    },
+    /// The pattern is well formed but has no SQL translation.
+    UnsupportedPattern(String),
+    /// The statement is not one the converter handles.
+    UnsupportedStatement(String),
+    /// The queries of a `UNION` return differently named columns.
+    UnionColumnMismatch {
+        left: Vec<String>,
+        right: Vec<String>,
+    },
+    /// A `SKIP` or `LIMIT` is given a negative row count.
+    InvalidLimit(String),
+    /// A `RETURN DISTINCT` returns only aggregates, and so a single row that
+    /// `DISTINCT` cannot deduplicate. Holds the returned items.
+    InvalidDistinctAggregate(String),
+    /// The input could not be parsed.
+    ParseError(ParserError),
+}
+
+impl fmt::Display for CypherConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CypherConversionError::InvalidPattern {
+                message,
+                position: Some(position),
+            } => write!(f, "invalid pattern at position {position}: {message}"),
+            CypherConversionError::InvalidPattern {
+                message,
+                position: None,
+            } => write!(f, "invalid pattern: {message}"),
+            CypherConversionError::UnsupportedPattern(msg) => {
+                write!(f, "unsupported pattern: {msg}")
+            }
+            CypherConversionError::UnsupportedStatement(msg) => {
+                write!(f, "unsupported statement: {msg}")
+            }
+            CypherConversionError::UnionColumnMismatch { left, right } => write!(
+                f,
+                "UNION queries return different columns: ({}) and ({})",
+                left.join(", "),
+                right.join(", ")
+            ),
+            CypherConversionError::InvalidLimit(clause) => {
+                write!(f, "invalid row count: {clause} must not be negative")
+            }
+            CypherConversionError::InvalidDistinctAggregate(items) => write!(
+                f,
+                "RETURN DISTINCT {items} returns a single row, so DISTINCT has no effect; \
+                 to aggregate distinct values, write e.g. count(DISTINCT x)"
+            ),
+            CypherConversionError::ParseError(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CypherConversionError {}
+
+impl CypherConversionError {
+    fn invalid_pattern(message: impl Into<String>) -> Self {
+        CypherConversionError::InvalidPattern {
+            message: message.into(),
+            position: None,
+        }
+    }
+}
+
+impl From<ParserError> for CypherConversionError {
+    fn from(e: ParserError) -> Self {
+        CypherConversionError::ParseError(e)
+    }
+}
+
+/// Note on a conversion that may not carry the meaning of the Cypher
+/// statement over to every database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionWarning {
+    /// The literal of a `STARTS WITH`, `ENDS WITH` or `CONTAINS` contains
+    /// the `LIKE` wildcards `%` or `_`. They are escaped with `ESCAPE '\'`,
+    /// which the database must support for them to match literally.
+    EscapedLikeWildcards {
+        operator: CypherStringOperator,
+        pattern: String,
+    },
+}
+
+impl fmt::Display for ConversionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConversionWarning::EscapedLikeWildcards { operator, pattern } => write!(
+                f,
+                "{operator} '{pattern}' escapes LIKE wildcards with ESCAPE '\\'"
+            ),
+        }
+    }
+}
+
+/// Parses and converts a single Cypher statement to SQL text.
+///
+/// This never panics: malformed or unsupported input, including arbitrary
+/// non-Cypher text, is reported as an error.
+pub fn try_convert(cypher: &str) -> Result<String, CypherConversionError> {
+    try_convert_with_options(cypher, &ConversionOptions::default())
+}
+
+/// Parses and converts a single Cypher statement to SQL text, as
+/// configured by `options`.
+pub fn try_convert_with_options(
+    cypher: &str,
+    options: &ConversionOptions,
+) -> Result<String, CypherConversionError> {
+    let statement = parse_single_statement(cypher)?;
+    let statements = convert_statements(&statement, options)?;
+    Ok(statements_text(&statements, options))
+}
+
+/// Parses and converts a single Cypher statement to SQL text, as
+/// configured by `options`, along with the [`conversion_warnings`] of the
+/// statement.
+pub fn try_convert_with_warnings(
+    cypher: &str,
+    options: &ConversionOptions,
+) -> Result<(String, Vec<ConversionWarning>), CypherConversionError> {
+    let statement = parse_single_statement(cypher)?;
+    let statements = convert_statements(&statement, options)?;
+    Ok((
+        statements_text(&statements, options),
+        conversion_warnings(&statement),
+    ))
+}
+
+fn statements_text(statements: &[Statement], options: &ConversionOptions) -> String {
+    statements
+        .iter()
+        .map(|sql| {
+            if options.pretty {
+                format!("{sql:#}")
+            } else {
+                sql.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(if options.pretty { ";\n" } else { "; " })
+}
+
+/// Returns warnings about the parts of a parsed Cypher statement whose
+/// conversion may not carry their meaning over to every database.
+pub fn conversion_warnings(statement: &Statement) -> Vec<ConversionWarning> {
+    let mut warnings = vec![];
+    statement_warnings(statement, &mut warnings);
+    warnings
+}
+
+fn statement_warnings(statement: &Statement, warnings: &mut Vec<ConversionWarning>) {
+    let item_warnings = |items: &[SelectItem], warnings: &mut Vec<ConversionWarning>| {
+        for item in items {
+            if let SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } = item {
+                expr_warnings(expr, warnings);
+            }
+        }
+    };
+    match statement {
+        Statement::CypherQuery {
+            where_clause,
+            return_items,
+            order_by,
+            ..
+        } => {
+            if let Some(where_clause) = where_clause {
+                expr_warnings(where_clause, warnings);
+            }
+            item_warnings(return_items, warnings);
+            for order_by in order_by {
+                expr_warnings(&order_by.expr, warnings);
+            }
+        }
+        Statement::CypherDelete {
+            where_clause,
+            return_items,
+            ..
+        } => {
+            if let Some(where_clause) = where_clause {
+                expr_warnings(where_clause, warnings);
+            }
+            item_warnings(return_items, warnings);
+        }
+        Statement::CypherSet {
+            where_clause,
+            items,
+            ..
+        } => {
+            if let Some(where_clause) = where_clause {
+                expr_warnings(where_clause, warnings);
+            }
+            for item in items {
+                match item {
+                    CypherSetItem::Property { value, .. } => expr_warnings(value, warnings),
+                    CypherSetItem::Merge { properties, .. } => expr_warnings(properties, warnings),
+                }
+            }
+        }
+        Statement::CypherRemove { where_clause, .. }
+        | Statement::CypherMerge { where_clause, .. } => {
+            if let Some(where_clause) = where_clause {
+                expr_warnings(where_clause, warnings);
+            }
+        }
+        Statement::CypherUnion { left, right, .. }
+        | Statement::CypherCall {
+            subquery: left,
+            query: right,
+        } => {
+            statement_warnings(left, warnings);
+            statement_warnings(right, warnings);
+        }
+        Statement::CypherCreate {
+            query: Some(query), ..
+        } => statement_warnings(query, warnings),
+        _ => {}
+    }
+}
+
+fn expr_warnings(expr: &Expr, warnings: &mut Vec<ConversionWarning>) {
+    match expr {
+        Expr::CypherStringMatch {
+            expr,
+            operator,
+            pattern,
+        } => {
+            expr_warnings(expr, warnings);
+            match pattern.as_ref() {
+                Expr::Value(value) => match &value.value {
+                    Value::SingleQuotedString(text) if text.contains(['%', '_']) => {
+                        warnings.push(ConversionWarning::EscapedLikeWildcards {
+                            operator: *operator,
+                            pattern: text.clone(),
+                        })
+                    }
+                    _ => {}
+                },
+                pattern => expr_warnings(pattern, warnings),
+            }
+        }
+        Expr::BinaryOp { left, right, .. } => {
+            expr_warnings(left, warnings);
+            expr_warnings(right, warnings);
+        }
+        Expr::UnaryOp { expr, .. }
+        | Expr::Nested(expr)
+        | Expr::Cast { expr, .. }
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr) => expr_warnings(expr, warnings),
+        Expr::InList { expr, list, .. } => {
+            expr_warnings(expr, warnings);
+            list.iter().for_each(|item| expr_warnings(item, warnings));
+        }
+        Expr::Between {
+            expr, low, high, ..
+        } => {
+            for operand in [expr, low, high] {
+                expr_warnings(operand, warnings);
+            }
+        }
+        Expr::Case {
+            operand,
+            conditions,
+            else_result,
+            ..
+        } => {
+            for operand in [operand, else_result].into_iter().flatten() {
+                expr_warnings(operand, warnings);
+            }
+            for when in conditions {
+                expr_warnings(&when.condition, warnings);
+                expr_warnings(&when.result, warnings);
+            }
+        }
+        Expr::Function(function) => {
+            if let FunctionArguments::List(list) = &function.args {
+                for arg in &list.args {
+                    if let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg {
+                        expr_warnings(expr, warnings);
+                    }
+                }
+            }
+        }
+        Expr::CypherExists {
+            where_clause: Some(where_clause),
+            ..
+        } => expr_warnings(where_clause, warnings),
+        Expr::CypherInSubquery { expr, query, .. } => {
+            expr_warnings(expr, warnings);
+            statement_warnings(query, warnings);
+        }
+        _ => {}
+    }
+}
+
+/// Returns the Cypher constructs used in `cypher` that the converter does
+/// not handle yet, such as `"variable-length path"` or `"OPTIONAL MATCH"`,
+/// each once, in order of first use.
+///
+/// The input is scanned rather than converted, so that every construct is
+/// reported even when the input doesn't parse. Input that can't be
+/// tokenized has nothing to report.
+pub fn analyze_unsupported(cypher: &str) -> Vec<String> {
+    let Ok(tokens) = Tokenizer::new(&GenericDialect {}, cypher).tokenize() else {
+        return vec![];
+    };
+    let tokens: Vec<Token> = tokens
+        .into_iter()
+        .filter(|token| !matches!(token, Token::Whitespace(_)))
+        .collect();
+
+    let keyword = |token: Option<&Token>, keyword: Keyword| matches!(token, Some(Token::Word(word)) if word.keyword == keyword);
+    let mut unsupported: Vec<&str> = vec![];
+    let mut relationship = false;
+    let mut created = false;
+    let mut matched = false;
+    for (index, token) in tokens.iter().enumerate() {
+        let before = |offset: usize| index.checked_sub(offset).and_then(|i| tokens.get(i));
+        let (previous, next) = (before(1), tokens.get(index + 1));
+        let construct = match token {
+            Token::LBracket if matches!(previous, Some(Token::Minus)) => {
+                relationship = true;
+                continue;
+            }
+            Token::RBracket => {
+                relationship = false;
+                continue;
+            }
+            Token::Mul if relationship => "variable-length path",
+            Token::LBracket if matches!(next, Some(Token::LParen)) => "pattern comprehension",
+            Token::Word(word) => match word.value.to_ascii_uppercase().as_str() {
+                "CREATE" => {
+                    created = true;
+                    continue;
+                }
+                "MATCH" => {
+                    matched = true;
+                    continue;
+                }
+                "OPTIONAL" if keyword(next, Keyword::MATCH) => "OPTIONAL MATCH",
+                "UNWIND" => "UNWIND",
+                // `MATCH ... MERGE (a)-[:KNOWS]->(b)` is converted
+                "MERGE" if !matched => "MERGE",
+                "FOREACH" => "FOREACH",
+                // `CREATE ... WITH n MATCH ...` is converted, unlike a `WITH`
+                // passing on the rows of a `MATCH`.
+                "WITH"
+                    if !created
+                        && !keyword(previous, Keyword::STARTS)
+                        && !matches!(previous, Some(Token::Word(word))
+                            if word.value.eq_ignore_ascii_case("ENDS")) =>
+                {
+                    "WITH"
+                }
+                "SHORTESTPATH" | "ALLSHORTESTPATHS" if matches!(next, Some(Token::LParen)) => {
+                    "shortest path"
+                }
+                _ => continue,
+            },
+            _ => continue,
+        };
+        if !unsupported.contains(&construct) {
+            unsupported.push(construct);
+        }
+    }
+    unsupported.into_iter().map(str::to_string).collect()
+}
+
+/// Tables and columns known to the database that converted statements are
+/// meant to run on, for [`validate_against_schema`]. Implement it for a
+/// wrapper of a catalog such as DataFusion's `DFSchema`.
+#[cfg(feature = "schema-validation")]
+pub trait TableSchema {
+    /// Whether the table `table` exists.
+    fn has_table(&self, table: &str) -> bool;
+    /// Whether the table `table` has the column `column`.
+    fn has_column(&self, table: &str, column: &str) -> bool;
+}
+
+/// Columns of each table, keyed by table name.
+#[cfg(feature = "schema-validation")]
+impl TableSchema for HashMap<String, Vec<String>> {
+    fn has_table(&self, table: &str) -> bool {
+        self.contains_key(table)
+    }
+
+    fn has_column(&self, table: &str, column: &str) -> bool {
+        self.get(table)
+            .is_some_and(|columns| columns.iter().any(|c| c == column))
+    }
+}
+
+/// Checks that the tables of a converted SQL statement, and the columns it
+/// qualifies with their alias or inserts into, exist in `schema`. This
+/// catches labels and properties that don't map to the schema before the
+/// statement runs. Tables are named as written, with any schema, e.g.
+/// `analytics.persons`.
+///
+/// Unqualified columns, and the columns of derived tables and CTEs, are not
+/// checked.
+#[cfg(feature = "schema-validation")]
+pub fn validate_against_schema(
+    statement: &Statement,
+    schema: &dyn TableSchema,
+) -> Result<(), String> {
+    let mut references = SchemaReferences::default();
+    let _ = statement.visit(&mut references);
+
+    for table in &references.tables {
+        if !references.ctes.contains(table) && !schema.has_table(table) {
+            return Err(format!("unknown table {table}"));
+        }
+    }
+    for (table, column) in &references.inserted_columns {
+        if !schema.has_column(table, column) {
+            return Err(format!("unknown column {table}.{column}"));
+        }
+    }
+    for (qualifier, column) in &references.qualified_columns {
+        let Some(table) = references.aliases.get(qualifier) else {
+            continue;
+        };
+        if !references.ctes.contains(table) && !schema.has_column(table, column) {
+            return Err(format!("unknown column {table}.{column}"));
+        }
+    }
+    Ok(())
+}
+
+/// The tables and columns a statement references, collected for
+/// [`validate_against_schema`].
+#[cfg(feature = "schema-validation")]
+#[derive(Default)]
+struct SchemaReferences {
+    /// Names of the CTEs defined anywhere in the statement.
+    ctes: std::collections::HashSet<String>,
+    /// Tables read or written, in the order they appear.
+    tables: Vec<String>,
+    /// The table named by each alias, or by its own name when unaliased.
+    aliases: HashMap<String, String>,
+    /// Columns of `INSERT INTO <table> (<columns>)`, with their table.
+    inserted_columns: Vec<(String, String)>,
+    /// Columns qualified by an alias, with the alias.
+    qualified_columns: Vec<(String, String)>,
+}
+
+#[cfg(feature = "schema-validation")]
+impl Visitor for SchemaReferences {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> core::ops::ControlFlow<()> {
+        if let Some(with) = &query.with {
+            self.ctes.extend(
+                with.cte_tables
+                    .iter()
+                    .map(|cte| cte.alias.name.value.clone()),
+            );
+        }
+        core::ops::ControlFlow::Continue(())
+    }
+
+    fn pre_visit_table_factor(&mut self, table_factor: &TableFactor) -> core::ops::ControlFlow<()> {
+        if let TableFactor::Table { name, alias, .. } = table_factor {
+            let table = schema_table_name(name);
+            let alias = alias
+                .as_ref()
+                .map_or_else(|| table.clone(), |alias| alias.name.value.clone());
+            self.aliases.insert(alias, table.clone());
+            self.tables.push(table);
+        }
+        core::ops::ControlFlow::Continue(())
+    }
+
+    fn pre_visit_statement(&mut self, statement: &Statement) -> core::ops::ControlFlow<()> {
+        if let Statement::Insert(Insert {
+            table: TableObject::TableName(name),
+            columns,
+            ..
+        }) = statement
+        {
+            let table = schema_table_name(name);
+            self.inserted_columns.extend(
+                columns
+                    .iter()
+                    .map(|column| (table.clone(), column.value.clone())),
+            );
+            self.tables.push(table);
+        }
+        core::ops::ControlFlow::Continue(())
+    }
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> core::ops::ControlFlow<()> {
+        if let Expr::CompoundIdentifier(parts) = expr {
+            if let [qualifier @ .., column] = parts.as_slice() {
+                if !qualifier.is_empty() {
+                    let qualifier = qualifier
+                        .iter()
+                        .map(|part| part.value.as_str())
+                        .collect::<Vec<_>>()
+                        .join(".");
+                    self.qualified_columns
+                        .push((qualifier, column.value.clone()));
+                }
+            }
+        }
+        core::ops::ControlFlow::Continue(())
+    }
+}
+
+/// Returns a table name as [`TableSchema`] looks it up: its parts, unquoted,
+/// joined by `.`.
+#[cfg(feature = "schema-validation")]
+fn schema_table_name(name: &ObjectName) -> String {
+    name.0
+        .iter()
+        .map(|part| match part.as_ident() {
+            Some(ident) => ident.value.clone(),
+            None => part.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Parses and converts a single Cypher statement to SQL text in which the
+/// literal values of inline property maps are replaced by `?` placeholders,
+/// returned alongside in placeholder order.
+pub fn convert_to_parameterized(
+    cypher: &str,
+) -> Result<(String, Vec<Value>), CypherConversionError> {
+    let mut statement = parse_single_statement(cypher)?;
+    let mut values = vec![];
+    parameterize_statement(&mut statement, &mut values)?;
+    let sql = convert_statement(&statement, &ConversionOptions::default())?;
+    Ok((sql.to_string(), values))
+}
+
+/// Replaces the literal property values in the patterns of a statement by
+/// placeholders, in the order the converted SQL will reference them.
+fn parameterize_statement(
+    statement: &mut Statement,
+    values: &mut Vec<Value>,
+) -> Result<(), CypherConversionError> {
+    match statement {
+        Statement::CypherQuery { pattern, .. } | Statement::CypherDelete { pattern, .. }
+            if !pattern.is_empty() =>
+        {
+            parameterize_pattern(pattern, values)
+        }
+        Statement::CypherCreate { pattern, query, .. } => {
+            parameterize_pattern(pattern, values)?;
+            match query {
+                Some(query) => parameterize_statement(query, values),
+                None => Ok(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+fn parameterize_pattern(
+    pattern: &mut String,
+    values: &mut Vec<Value>,
+) -> Result<(), CypherConversionError> {
+    let mut graph = GraphPattern::parse(pattern)?;
+    let properties =
+        graph.paths.iter_mut().flat_map(|path| {
+            core::iter::once(&mut path.start.properties).chain(path.steps.iter_mut().flat_map(
+                |(relationship, node)| [&mut relationship.properties, &mut node.properties],
+            ))
+        });
+    for (_, value) in properties.flatten() {
+        if let Expr::Value(value) = value {
+            if !matches!(value.value, Value::Placeholder(_)) {
+                values.push(core::mem::replace(
+                    &mut value.value,
+                    Value::Placeholder("?".to_string()),
+                ));
+            }
+        }
+    }
+    *pattern = graph.to_string();
+    Ok(())
+}
+
+/// Parses and converts a single Cypher statement to the equivalent SQL
+/// statement.
+pub fn convert_to_statement(
+    cypher: &str,
+    options: &ConversionOptions,
+) -> Result<Statement, CypherConversionError> {
+    convert_statement(&parse_single_statement(cypher)?, options)
+}
+
+/// Memoizes the conversions of a [`CypherConverter`], keyed by the Cypher
+/// text and the options it is converted with, keeping the results of the
+/// `capacity` most recently used inputs.
+#[cfg(feature = "conversion-cache")]
+#[derive(Debug)]
+pub struct CachedConverter<C = DefaultConverter> {
+    converter: C,
+    capacity: usize,
+    /// Cached conversions, least recently used first.
+    entries: std::collections::VecDeque<(
+        String,
+        ConversionOptions,
+        Result<Statement, CypherConversionError>,
+    )>,
+}
+
+#[cfg(feature = "conversion-cache")]
+impl CachedConverter {
+    /// Creates a cache of the built-in conversions.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_converter(DefaultConverter, capacity)
+    }
+}
+
+#[cfg(feature = "conversion-cache")]
+impl<C: CypherConverter> CachedConverter<C> {
+    /// Creates a cache of the conversions of `converter`.
+    pub fn with_converter(converter: C, capacity: usize) -> Self {
+        CachedConverter {
+            converter,
+            capacity,
+            entries: Default::default(),
+        }
+    }
+
+    /// Parses and converts a single Cypher statement like
+    /// [`convert_to_statement`], reusing the result of an earlier call with
+    /// the same input when it is still cached.
+    pub fn convert_to_statement(
+        &mut self,
+        cypher: &str,
+        options: &ConversionOptions,
+    ) -> Result<Statement, CypherConversionError> {
+        let cached = self
+            .entries
+            .iter()
+            .position(|(text, text_options, _)| text == cypher && text_options == options);
+        if let Some(entry) = cached.and_then(|index| self.entries.remove(index)) {
+            let result = entry.2.clone();
+            self.entries.push_back(entry);
+            return result;
+        }
+
+        let result = parse_single_statement(cypher).and_then(|statement| {
+            convert_statement_with_converter(&statement, options, &self.converter)
+        });
+        if self.capacity > 0 {
+            if self.entries.len() == self.capacity {
+                self.entries.pop_front();
+            }
+            self.entries
+                .push_back((cypher.to_string(), options.clone(), result.clone()));
+        }
+        result
+    }
+}
+
+/// Describes how the pattern of a single Cypher statement maps to tables,
+/// without converting the rest of the statement: one line per table the
+/// rows come from, per table joined to it and per filter on the joined
+/// rows, in the order the SQL would list them. For example,
+/// `MATCH (a:Person)-[:KNOWS]->(b:Person) WHERE b.age > 18 RETURN b` is
+/// planned as:
+///
+/// ```text
+/// table: Person AS a
+/// join: KNOWS AS _r1 ON _r1.source_id = a.id
+/// join: Person AS b ON b.id = _r1.target_id
+/// filter: b.age > 18
+/// ```
+pub fn plan_only(cypher: &str) -> Result<String, CypherConversionError> {
+    let statement = parse_single_statement(cypher)?;
+    let (pattern, where_clause) = match &statement {
+        Statement::CypherQuery {
+            pattern,
+            where_clause,
+            ..
+        }
+        | Statement::CypherDelete {
+            pattern,
+            where_clause,
+            ..
+        }
+        | Statement::CypherRemove {
+            pattern,
+            where_clause,
+            ..
+        }
+        | Statement::CypherSet {
+            pattern,
+            where_clause,
+            ..
+        }
+        | Statement::CypherMerge {
+            pattern,
+            where_clause,
+            ..
+        } => (pattern, where_clause),
+        _ => {
+            return Err(CypherConversionError::UnsupportedStatement(
+                "only the pattern of a MATCH can be planned".to_string(),
+            ))
+        }
+    };
+
+    let options = ConversionOptions::default();
+    let mut context = ConversionContext::new(&options);
+    let PatternTables {
+        from,
+        predicates,
+        property_predicates,
+    } = if pattern.is_empty() {
+        PatternTables::default()
+    } else {
+        graph_to_from(&mut context, &GraphPattern::parse(pattern)?)?
+    };
+    let where_clause = where_clause
+        .as_ref()
+        .map(|expr| convert_expr(&mut context, expr))
+        .transpose()?;
+
+    let mut plan = vec![];
+    for table in from {
+        plan.push(format!("table: {}", table.relation));
+        for join in table.joins {
+            match join.join_operator {
+                JoinOperator::Join(JoinConstraint::On(on)) => {
+                    plan.push(format!("join: {} ON {on}", join.relation))
+                }
+                _ => plan.push(format!("join: {}", join.relation)),
+            }
+        }
+    }
+    for filter in predicates
+        .into_iter()
+        .chain(property_predicates)
+        .chain(where_clause)
+    {
+        plan.push(format!("filter: {filter}"));
+    }
+    Ok(plan.join("\n"))
+}
+
+/// Parses and converts a bare Cypher predicate, such as the text after a
+/// `WHERE`, to a SQL expression over a single existing table.
+///
+/// Property references such as `n.age` are qualified with `alias` instead
+/// of their variable, or left unqualified if `alias` is `None`.
+pub fn convert_where(
+    cypher_where: &str,
+    alias: Option<&str>,
+) -> Result<Expr, CypherConversionError> {
+    convert_where_with_options(cypher_where, alias, &ConversionOptions::default())
+}
+
+/// Like [`convert_where`], converting as `options` ask.
+pub fn convert_where_with_options(
+    cypher_where: &str,
+    alias: Option<&str>,
+    options: &ConversionOptions,
+) -> Result<Expr, CypherConversionError> {
+    let tokens = Tokenizer::new(&GenericDialect {}, cypher_where)
+        .tokenize()
+        .map_err(ParserError::from)?;
+
+    let mut requalified: Vec<Token> = vec![];
+    let mut index = 0;
+    while let Some(token) = tokens.get(index) {
+        index += 1;
+        // A dotted function name, as in `point.distance(a, b)`, is not a
+        // property reference.
+        let is_variable = matches!(token, Token::Word(_))
+            && tokens.get(index) == Some(&Token::Period)
+            && requalified.last() != Some(&Token::Period)
+            && tokens.get(index + 2) != Some(&Token::LParen);
+        if !is_variable {
+            requalified.push(token.clone());
+            continue;
+        }
+        match alias {
+            Some(alias) => requalified.push(Token::make_word(alias, None)),
+            None => index += 1,
+        }
+    }
+
+    let mut parser = Parser::new(&GenericDialect {}).with_tokens(requalified);
+    let expr = parser.parse_cypher_expr()?;
+    parser.expect_token(&Token::EOF)?;
+
+    convert_expr(&mut ConversionContext::new(options), &expr)
+}
+
+/// Parses Cypher text into statements such as [`Statement::CypherQuery`],
+/// ready to pass to [`convert_statement`].
+pub fn parse_cypher(input: &str) -> Result<Vec<Statement>, ParserError> {
+    Parser::parse_sql(&GenericDialect {}, input)
+}
+
+fn parse_single_statement(cypher: &str) -> Result<Statement, CypherConversionError> {
+    let mut statements = parse_cypher(cypher)?;
+    if statements.len() != 1 {
+        return Err(CypherConversionError::UnsupportedStatement(format!(
+            "expected a single statement, found {}",
+            statements.len()
+        )));
+    }
+    Ok(statements.remove(0))
+}
+
+/// Stages of converting a parsed Cypher statement to SQL, one per kind of
+/// statement. Each stage defaults to the built-in conversion, so that an
+/// implementation can override one stage, such as `CREATE`, and reuse the
+/// rest.
+pub trait CypherConverter {
+    /// Converts a `MATCH` query, each query of a `UNION` or `CALL`
+    /// subquery, or a bare `RETURN` to a query.
+    fn convert_match(
+        &self,
+        statement: &Statement,
+        options: &ConversionOptions,
+    ) -> Result<Statement, CypherConversionError> {
+        convert_single_statement(statement, options)
+    }
+
+    /// Converts a `CREATE`, along with any query following its `WITH`.
+    fn convert_create(
+        &self,
+        statement: &Statement,
+        options: &ConversionOptions,
+    ) -> Result<Statement, CypherConversionError> {
+        convert_single_statement(statement, options)
+    }
+
+    /// Converts a `[DETACH] DELETE` to the statements carrying it out.
+    fn convert_delete(
+        &self,
+        statement: &Statement,
+        options: &ConversionOptions,
+    ) -> Result<Vec<Statement>, CypherConversionError> {
+        let Statement::CypherDelete {
+            pattern,
+            where_clause,
+            detach,
+            variables,
+            return_items,
+        } = statement
+        else {
+            return Err(CypherConversionError::UnsupportedStatement(
+                "expected a DELETE".to_string(),
+            ));
+        };
+        cypher_delete_to_sql(
+            pattern,
+            where_clause,
+            *detach,
+            variables,
+            return_items,
+            options,
+        )
+    }
+
+    /// Converts a `REMOVE` to the statements carrying it out.
+    fn convert_remove(
+        &self,
+        statement: &Statement,
+        options: &ConversionOptions,
+    ) -> Result<Vec<Statement>, CypherConversionError> {
+        let Statement::CypherRemove {
+            pattern,
+            where_clause,
+            items,
+        } = statement
+        else {
+            return Err(CypherConversionError::UnsupportedStatement(
+                "expected a REMOVE".to_string(),
+            ));
+        };
+        cypher_remove_to_sql(pattern, where_clause, items, options)
+    }
+
+    /// Converts a `SET` to the statements carrying it out.
+    fn convert_set(
+        &self,
+        statement: &Statement,
+        options: &ConversionOptions,
+    ) -> Result<Vec<Statement>, CypherConversionError> {
+        let Statement::CypherSet {
+            pattern,
+            where_clause,
+            items,
+        } = statement
+        else {
+            return Err(CypherConversionError::UnsupportedStatement(
+                "expected a SET".to_string(),
+            ));
+        };
+        cypher_set_to_sql(pattern, where_clause, items, options)
+    }
+
+    /// Converts a `MERGE` of a relationship to an upsert of its edge row.
+    fn convert_merge(
+        &self,
+        statement: &Statement,
+        options: &ConversionOptions,
+    ) -> Result<Statement, CypherConversionError> {
+        let Statement::CypherMerge {
+            pattern,
+            where_clause,
+            merge,
+        } = statement
+        else {
+            return Err(CypherConversionError::UnsupportedStatement(
+                "expected a MERGE".to_string(),
+            ));
+        };
+        cypher_merge_to_sql(pattern, where_clause, merge, options)
+    }
+}
+
+/// The built-in conversion of every kind of statement.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultConverter;
+
+impl CypherConverter for DefaultConverter {}
+
+/// Converts a parsed Cypher statement to the equivalent SQL statement.
+///
+/// Statements that convert to several SQL statements, such as
+/// `DETACH DELETE`, are rejected; use [`convert_statements`] for those.
+pub fn convert_statement(
+    statement: &Statement,
+    options: &ConversionOptions,
+) -> Result<Statement, CypherConversionError> {
+    convert_statement_with_converter(statement, options, &DefaultConverter)
+}
+
+/// Converts a parsed Cypher statement to the equivalent SQL statement, with
+/// the stages of `converter`.
+pub fn convert_statement_with_converter(
+    statement: &Statement,
+    options: &ConversionOptions,
+    converter: &dyn CypherConverter,
+) -> Result<Statement, CypherConversionError> {
+    let mut statements = convert_statements_with_converter(statement, options, converter)?;
+    if statements.len() != 1 {
+        return Err(CypherConversionError::UnsupportedStatement(format!(
+            "converts to {} SQL statements",
+            statements.len()
+        )));
+    }
+    Ok(statements.remove(0))
+}
+
+/// Converts a parsed Cypher statement to the SQL statements to run, in
+/// order, to carry it out.
+pub fn convert_statements(
+    statement: &Statement,
+    options: &ConversionOptions,
+) -> Result<Vec<Statement>, CypherConversionError> {
+    convert_statements_with_converter(statement, options, &DefaultConverter)
+}
+
+/// Converts a parsed Cypher statement to the SQL statements to run, in
+/// order, to carry it out, with the stages of `converter`.
+pub fn convert_statements_with_converter(
+    statement: &Statement,
+    options: &ConversionOptions,
+    converter: &dyn CypherConverter,
+) -> Result<Vec<Statement>, CypherConversionError> {
+    let mut statements = match statement {
+        Statement::CypherDelete { .. } => converter.convert_delete(statement, options)?,
+        Statement::CypherRemove { .. } => converter.convert_remove(statement, options)?,
+        Statement::CypherSet { .. } => converter.convert_set(statement, options)?,
+        Statement::CypherMerge { .. } => vec![converter.convert_merge(statement, options)?],
+        Statement::CypherCreate { .. } => vec![converter.convert_create(statement, options)?],
+        Statement::CypherUnion { .. } => {
+            let (body, _) = convert_union(statement, options, converter)?;
+            vec![Statement::Query(Box::new(create_query(body)))]
+        }
+        Statement::CypherCall { subquery, query } => {
+            vec![convert_call(subquery, query, options, converter)?]
+        }
+        _ => vec![converter.convert_match(statement, options)?],
+    };
+    if options.ansi_quoting {
+        statements.iter_mut().for_each(quote_identifiers);
+    }
+    Ok(statements)
+}
+
+fn convert_single_statement(
+    statement: &Statement,
+    options: &ConversionOptions,
+) -> Result<Statement, CypherConversionError> {
+    match statement {
+        Statement::CypherQuery { .. } => {
+            let mut context = ConversionContext::new(options);
+            let query = convert_match(&mut context, statement)?;
+            Ok(Statement::Query(Box::new(query)))
+        }
+        Statement::CypherUnion { .. } => {
+            let (body, _) = convert_union(statement, options, &DefaultConverter)?;
+            Ok(Statement::Query(Box::new(create_query(body))))
+        }
+        Statement::CypherCall { subquery, query } => {
+            convert_call(subquery, query, options, &DefaultConverter)
+        }
+        Statement::CypherCreate {
+            pattern,
+            with,
+            query: None,
+        } if with.is_empty() => cypher_create_to_sql_with_options(pattern, options),
+        Statement::CypherCreate {
+            pattern,
+            with,
+            query: Some(query),
+        } => cypher_create_with_to_sql(pattern, with, query, options),
+        Statement::Return(ReturnStatement {
+            value: Some(ReturnStatementValue::Expr(expr)),
+        }) => {
+            let mut context = ConversionContext::new(options);
+            let projection = vec![SelectItem::UnnamedExpr(convert_expr(&mut context, expr)?)];
+            let select = create_select(projection, options.target_dialect.constant_from(), None);
+            Ok(Statement::Query(Box::new(create_query(SetExpr::Select(
+                Box::new(select),
+            )))))
+        }
+        _ => Err(CypherConversionError::UnsupportedStatement(
+            "not a Cypher statement".to_string(),
+        )),
+    }
+}
 
 pub fn cypher_to_sql(
     pattern: &str,
     where_clause: &Option<Expr>,
     return_items: &[SelectItem],
-) -> Result<Statement, String> {
-    let table_name = extract_first_label(pattern)?;
-    let table_alias = extract_first_variable(pattern);
-
-    let from_table = TableWithJoins {
-        relation: TableFactor::Table {
-            name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new(table_name))]),
-            alias: table_alias.map(|var| TableAlias{
-                name: Ident::new(var),
-                columns: vec![],
-            }),
-            args: None,
-            with_hints: vec![],
-            version: None,
-            with_ordinality: false,
-            partitions: vec![],
-            json_path: None,
-            index_hints: vec![],
-            sample: None,
+) -> Result<Statement, CypherConversionError> {
+    cypher_to_sql_with_options(
+        pattern,
+        where_clause,
+        return_items,
+        &ConversionOptions::default(),
+    )
+}
+
+/// Converts a `MATCH` query to a `SELECT`, joining the node and edge tables
+/// of every relationship in the pattern.
+pub fn cypher_to_sql_with_options(
+    pattern: &str,
+    where_clause: &Option<Expr>,
+    return_items: &[SelectItem],
+    options: &ConversionOptions,
+) -> Result<Statement, CypherConversionError> {
+    let statement = Statement::CypherQuery {
+        pattern: pattern.to_string(),
+        where_clause: where_clause.clone(),
+        distinct: false,
+        return_items: return_items.to_vec(),
+        order_by: vec![],
+        skip: None,
+        limit: None,
+    };
+    convert_statement(&statement, options)
+}
+
+fn convert_match(
+    context: &mut ConversionContext,
+    statement: &Statement,
+) -> Result<Query, CypherConversionError> {
+    let Statement::CypherQuery {
+        pattern,
+        where_clause,
+        distinct,
+        return_items,
+        order_by,
+        skip,
+        limit,
+    } = statement
+    else {
+        return Err(CypherConversionError::UnsupportedStatement(
+            "expected a MATCH query".to_string(),
+        ));
+    };
+
+    // A bare `RETURN` of constants selects from no table at all.
+    let PatternTables {
+        from,
+        predicates,
+        property_predicates,
+    } = if pattern.is_empty() {
+        PatternTables {
+            from: context.options.target_dialect.constant_from(),
+            ..Default::default()
+        }
+    } else {
+        graph_to_from(context, &GraphPattern::parse(pattern)?)?
+    };
+
+    let sql_projection = convert_return_items(context, return_items)?;
+    let group_by = implicit_group_by(return_items, &sql_projection)?;
+    if *distinct
+        && group_by.is_empty()
+        && return_items.iter().any(|item| {
+            matches!(item, SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. }
+                if is_aggregate(expr))
+        })
+    {
+        return Err(CypherConversionError::InvalidDistinctAggregate(
+            display_comma_separated(return_items).to_string(),
+        ));
+    }
+
+    let where_clause = where_clause
+        .as_ref()
+        .map(|expr| convert_expr(context, expr))
+        .transpose()?;
+    let selection = selection(
+        context.options,
+        predicates,
+        property_predicates,
+        where_clause,
+    );
+
+    let mut select = create_select(sql_projection, from, selection);
+    if *distinct {
+        select.distinct = Some(Distinct::Distinct);
+    }
+    select.group_by = GroupByExpr::Expressions(group_by, vec![]);
+
+    let mut query = create_query(SetExpr::Select(Box::new(select)));
+    if !order_by.is_empty() {
+        query.order_by = Some(OrderBy {
+            kind: OrderByKind::Expressions(
+                order_by
+                    .iter()
+                    .map(|order_by_expr| {
+                        let mut order_by = OrderByExpr {
+                            expr: convert_expr(context, &order_by_expr.expr)?,
+                            ..order_by_expr.clone()
+                        };
+                        if order_by.options.nulls_first.is_none() {
+                            order_by.options.nulls_first = context
+                                .options
+                                .nulls_ordering
+                                .map(|nulls| nulls == NullsOrdering::First);
+                        }
+                        Ok(context.options.target_dialect.order_by_nulls(order_by))
+                    })
+                    .collect::<Result<Vec<_>, CypherConversionError>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            ),
+            interpolate: None,
+        });
+    }
+    if skip.is_some() || limit.is_some() {
+        query.limit_clause = Some(LimitClause::LimitOffset {
+            limit: limit
+                .as_ref()
+                .map(|limit| row_count(context, "LIMIT", limit))
+                .transpose()?,
+            offset: skip
+                .as_ref()
+                .map(|skip| row_count(context, "SKIP", skip))
+                .transpose()?
+                .map(|value| Offset {
+                    value,
+                    rows: OffsetRows::None,
+                }),
+            limit_by: vec![],
+        });
+    }
+    if *distinct
+        && context.options.distinct_on
+        && context.options.target_dialect == TargetDialect::PostgreSql
+    {
+        distinct_on_first_item(&mut query);
+    }
+    if context.options.emit_pipe {
+        query = pipe_query(query);
+    }
+    Ok(query)
+}
+
+/// Replaces the `DISTINCT` of a converted `SELECT` by a `DISTINCT ON` its
+/// first item, unless the query is ordered by another item first.
+fn distinct_on_first_item(query: &mut Query) {
+    let leading_order = match &query.order_by {
+        Some(OrderBy {
+            kind: OrderByKind::Expressions(exprs),
+            ..
+        }) => exprs.first().map(|order_by| &order_by.expr),
+        _ => None,
+    };
+    let SetExpr::Select(select) = query.body.as_mut() else {
+        return;
+    };
+    let key = match select.projection.first() {
+        Some(SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. }) => expr,
+        _ => return,
+    };
+    if leading_order.is_none() || leading_order == Some(key) {
+        select.distinct = Some(Distinct::On(vec![key.clone()]));
+    }
+}
+
+/// Rewrites a converted `SELECT` as its `FROM` followed by the pipe
+/// operators `WHERE`, `ORDER BY`, `LIMIT` and `SELECT`, in that order so
+/// that sorting can still refer to the matched nodes. A query with no pipe
+/// equivalent here is returned unchanged.
+fn pipe_query(mut query: Query) -> Query {
+    let limit = match &query.limit_clause {
+        None => None,
+        Some(LimitClause::LimitOffset {
+            limit: Some(limit),
+            offset,
+            limit_by,
+        }) if limit_by.is_empty() => Some(PipeOperator::Limit {
+            expr: limit.clone(),
+            offset: offset.as_ref().map(|offset| offset.value.clone()),
+        }),
+        Some(_) => return query,
+    };
+    let order_by = match &query.order_by {
+        None => None,
+        Some(OrderBy {
+            kind: OrderByKind::Expressions(exprs),
+            ..
+        }) => Some(PipeOperator::OrderBy {
+            exprs: exprs.clone(),
+        }),
+        Some(_) => return query,
+    };
+    let SetExpr::Select(select) = query.body.as_mut() else {
+        return query;
+    };
+    if select.distinct.is_some()
+        || select.from.is_empty()
+        || !matches!(&select.group_by, GroupByExpr::Expressions(exprs, _) if exprs.is_empty())
+    {
+        return query;
+    }
+
+    select.flavor = SelectFlavor::FromFirstNoSelect;
+    let projection = std::mem::take(&mut select.projection);
+    query.pipe_operators.extend(
+        select
+            .selection
+            .take()
+            .map(|expr| PipeOperator::Where { expr })
+            .into_iter()
+            .chain(order_by)
+            .chain(limit)
+            .chain([PipeOperator::Select { exprs: projection }]),
+    );
+    query.order_by = None;
+    query.limit_clause = None;
+    query
+}
+
+/// Converts the row count of a `SKIP` or `LIMIT`, which Cypher requires to
+/// be non-negative.
+fn row_count(
+    context: &mut ConversionContext,
+    clause: &str,
+    count: &Expr,
+) -> Result<Expr, CypherConversionError> {
+    let negative = match count {
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => matches!(expr.as_ref(), Expr::Value(_)),
+        Expr::Value(value) => {
+            matches!(&value.value, Value::Number(n, _) if n.to_string().starts_with('-'))
+        }
+        _ => false,
+    };
+    if negative {
+        return Err(CypherConversionError::InvalidLimit(format!(
+            "{clause} {count}"
+        )));
+    }
+    convert_expr(context, count)
+}
+
+/// Converts `CALL { <subquery> } RETURN <items>` to a `SELECT` from the
+/// converted subquery as a derived table. A subquery returning a single
+/// node, as in `CALL { MATCH (n:Person) RETURN n }`, is aliased by the
+/// node's variable so that the outer `RETURN` can refer to it.
+fn convert_call(
+    subquery: &Statement,
+    query: &Statement,
+    options: &ConversionOptions,
+    converter: &dyn CypherConverter,
+) -> Result<Statement, CypherConversionError> {
+    let Statement::Query(converted) =
+        convert_statement_with_converter(subquery, options, converter)?
+    else {
+        return Err(CypherConversionError::UnsupportedStatement(
+            "CALL subquery must be a query".to_string(),
+        ));
+    };
+
+    let mut context = ConversionContext::new(options);
+    let alias = match subquery {
+        Statement::CypherQuery { return_items, .. } => match return_items.as_slice() {
+            [SelectItem::UnnamedExpr(Expr::Identifier(variable))] => variable.value.clone(),
+            _ => context.aliases.alias(&None, "_q"),
+        },
+        _ => context.aliases.alias(&None, "_q"),
+    };
+
+    let Statement::CypherQuery { return_items, .. } = query else {
+        return Err(CypherConversionError::UnsupportedStatement(
+            "expected a RETURN after CALL".to_string(),
+        ));
+    };
+    let mut query = convert_match(&mut context, query)?;
+    if let SetExpr::Select(select) = query.body.as_mut() {
+        // Only the alias names a row; other variables name its columns.
+        for (item, projected) in return_items.iter().zip(&mut select.projection) {
+            if let SelectItem::UnnamedExpr(Expr::Identifier(variable)) = item {
+                if variable.value != alias {
+                    *projected = item.clone();
+                }
+            }
+        }
+        select.from = vec![TableWithJoins {
+            relation: TableFactor::Derived {
+                lateral: false,
+                subquery: converted,
+                alias: Some(TableAlias {
+                    name: Ident::new(alias),
+                    columns: vec![],
+                }),
+            },
+            joins: vec![],
+        }];
+    }
+    Ok(Statement::Query(Box::new(query)))
+}
+
+/// Converts `<query> UNION [ALL] <query>` to the `UNION` of the converted
+/// queries, along with the names of the columns they return. As in Cypher,
+/// every query must return the same column names, in the same order.
+fn convert_union(
+    statement: &Statement,
+    options: &ConversionOptions,
+    converter: &dyn CypherConverter,
+) -> Result<(SetExpr, Vec<String>), CypherConversionError> {
+    match statement {
+        Statement::CypherUnion { left, right, all } => {
+            let (left, left_columns) = convert_union(left, options, converter)?;
+            let (right, right_columns) = convert_union(right, options, converter)?;
+            if left_columns != right_columns {
+                return Err(CypherConversionError::UnionColumnMismatch {
+                    left: left_columns,
+                    right: right_columns,
+                });
+            }
+            let union = SetExpr::SetOperation {
+                op: SetOperator::Union,
+                set_quantifier: if *all {
+                    SetQuantifier::All
+                } else {
+                    SetQuantifier::None
+                },
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+            Ok((union, left_columns))
+        }
+        Statement::CypherQuery { return_items, .. } => {
+            let columns = return_items
+                .iter()
+                .map(|item| match item {
+                    SelectItem::ExprWithAlias { alias, .. } => alias.value.clone(),
+                    item => item.to_string(),
+                })
+                .collect();
+            let Statement::Query(query) = converter.convert_match(statement, options)? else {
+                return Err(CypherConversionError::UnsupportedStatement(
+                    "UNION query must convert to a query".to_string(),
+                ));
+            };
+            // A query ordering or limiting its own rows, or written as a
+            // pipe, keeps those clauses in parentheses.
+            let body = if query.order_by.is_some()
+                || query.limit_clause.is_some()
+                || !query.pipe_operators.is_empty()
+            {
+                SetExpr::Query(query)
+            } else {
+                *query.body
+            };
+            Ok((body, columns))
+        }
+        _ => Err(CypherConversionError::UnsupportedStatement(
+            "expected a MATCH query".to_string(),
+        )),
+    }
+}
+
+/// Converts `MATCH ... [DETACH] DELETE n` to a `DELETE` of each deleted
+/// node's rows, selected by id from the rows matching the pattern.
+///
+/// With `detach`, the rows referencing those nodes are first deleted from
+/// every edge table named in the pattern, in
+/// [`ConversionOptions::edge_tables`] or as
+/// [`ConversionOptions::default_edge_table`], so that all edge deletes
+/// precede the node deletes they would otherwise block.
+///
+/// Each delete empties tables the pattern joins, so when there are several,
+/// the ids of the deleted nodes are first read into a temporary table, which
+/// every delete selects from and which is dropped after the last one.
+///
+/// Items of a `RETURN` after the `DELETE` become the `RETURNING` clause of
+/// the delete of the node they refer to, e.g. `n.name` becomes `name`.
+///
+/// Without variables, as in `DELETE *`, every node of the pattern is
+/// deleted.
+pub fn cypher_delete_to_sql(
+    pattern: &str,
+    where_clause: &Option<Expr>,
+    detach: bool,
+    variables: &[Ident],
+    return_items: &[SelectItem],
+    options: &ConversionOptions,
+) -> Result<Vec<Statement>, CypherConversionError> {
+    let graph = GraphPattern::parse(pattern)?;
+
+    let mut edge_tables = vec![];
+    if detach {
+        let pattern_types = graph
+            .paths
+            .iter()
+            .flat_map(|path| &path.steps)
+            .flat_map(|(relationship, _)| &relationship.types);
+        for table in options
+            .edge_tables
+            .iter()
+            .chain(&options.default_edge_table)
+            .chain(pattern_types)
+        {
+            if !edge_tables.contains(table) {
+                edge_tables.push(table.clone());
+            }
+        }
+        if edge_tables.is_empty() {
+            return Err(CypherConversionError::UnsupportedPattern(
+                "DETACH DELETE requires known edge tables".to_string(),
+            ));
+        }
+    }
+
+    // `DELETE *` deletes every node of the pattern; a lone anonymous node,
+    // as in `MATCH (:Person {id: 1}) DELETE *`, is selected by its
+    // properties alone.
+    let mut deleted = vec![];
+    for node in graph
+        .paths
+        .iter()
+        .flat_map(|path| std::iter::once(&path.start).chain(path.steps.iter().map(|(_, n)| n)))
+    {
+        match &node.variable {
+            Some(variable) if variables.is_empty() => deleted.push((node, Some(variable.as_str()))),
+            None if variables.is_empty() => match graph.paths.as_slice() {
+                [path] if path.steps.is_empty() => deleted.push((node, None)),
+                _ => {
+                    return Err(CypherConversionError::UnsupportedPattern(
+                        "DELETE * of an anonymous node outside a lone node pattern".to_string(),
+                    ))
+                }
+            },
+            _ => {}
+        }
+    }
+    for variable in variables {
+        deleted.push((
+            pattern_node(&graph, variable)?,
+            Some(variable.value.as_str()),
+        ));
+    }
+
+    let mut returning: HashMap<&str, Vec<SelectItem>> = HashMap::new();
+    for item in return_items {
+        let (variable, item) = deleted_row_item(item)?;
+        if !deleted
+            .iter()
+            .any(|(_, deleted)| *deleted == Some(variable))
+        {
+            return Err(CypherConversionError::UnsupportedPattern(format!(
+                "RETURN after DELETE of {variable}, which is not deleted"
+            )));
+        }
+        returning.entry(variable).or_default().push(item);
+    }
+
+    let mut materialized = vec![];
+    if detach || deleted.len() > 1 {
+        for (_, variable) in &deleted {
+            if let Some(variable) = variable {
+                if !materialized.contains(variable) {
+                    materialized.push(*variable);
+                }
+            }
+        }
+    }
+    let mut statements = vec![];
+    if !materialized.is_empty() {
+        let projection = materialized
+            .iter()
+            .map(|variable| SelectItem::ExprWithAlias {
+                expr: column(variable, NODE_ID_COLUMN),
+                alias: Ident::new(*variable),
+            })
+            .collect();
+        let matched = matched_rows(options, &graph, where_clause, projection)?;
+        statements.push(
+            CreateTableBuilder::new(object_name(DELETED_NODES_TABLE))
+                .temporary(true)
+                .query(Some(Box::new(matched)))
+                .build(),
+        );
+    }
+
+    let mut edge_deletes = vec![];
+    let mut node_deletes = vec![];
+    for (node, variable) in deleted {
+        let mut context = ConversionContext::new(options);
+        let (matched, node_selection) = match variable {
+            Some(variable) if !materialized.is_empty() => (
+                create_query(SetExpr::Select(Box::new(create_select(
+                    vec![SelectItem::UnnamedExpr(Expr::Identifier(Ident::new(
+                        variable,
+                    )))],
+                    vec![TableWithJoins {
+                        relation: table_factor(DELETED_NODES_TABLE.to_string(), None),
+                        joins: vec![],
+                    }],
+                    None,
+                )))),
+                None,
+            ),
+            Some(variable) => (
+                matched_node_ids(options, &graph, where_clause, variable)?,
+                None,
+            ),
+            None => {
+                let mut property_predicates = vec![];
+                push_property_predicates(
+                    options,
+                    None,
+                    &node.properties,
+                    &mut property_predicates,
+                )?;
+                let where_clause = where_clause
+                    .as_ref()
+                    .map(|expr| convert_expr(&mut context, expr))
+                    .transpose()?;
+                let node_selection = selection(options, vec![], property_predicates, where_clause)
+                    .ok_or_else(|| {
+                        CypherConversionError::UnsupportedPattern(
+                            "DELETE * of an anonymous node without properties".to_string(),
+                        )
+                    })?;
+                let matched = create_query(SetExpr::Select(Box::new(create_select(
+                    vec![SelectItem::UnnamedExpr(Expr::Identifier(Ident::new(
+                        NODE_ID_COLUMN,
+                    )))],
+                    vec![TableWithJoins {
+                        relation: table_factor(node_table(&context, node)?, None),
+                        joins: vec![],
+                    }],
+                    Some(node_selection.clone()),
+                ))));
+                (matched, Some(node_selection))
+            }
+        };
+        let in_matched = |column_name: &str| Expr::InSubquery {
+            expr: Box::new(Expr::Identifier(Ident::new(column_name))),
+            subquery: Box::new(matched.clone()),
+            negated: false,
+        };
+
+        for table in &edge_tables {
+            edge_deletes.push(delete_from(
+                table.clone(),
+                or(
+                    in_matched(EDGE_SOURCE_COLUMN),
+                    in_matched(EDGE_TARGET_COLUMN),
+                ),
+            ));
+        }
+        let mut node_delete = delete_from(
+            node_table(&context, node)?,
+            node_selection.unwrap_or_else(|| in_matched(NODE_ID_COLUMN)),
+        );
+        if let (Statement::Delete(delete), Some(variable)) = (&mut node_delete, variable) {
+            delete.returning = returning.remove(variable);
+        }
+        node_deletes.push(node_delete);
+    }
+
+    statements.extend(edge_deletes);
+    statements.extend(node_deletes);
+    if !materialized.is_empty() {
+        statements.push(Statement::Drop {
+            object_type: ObjectType::Table,
+            if_exists: false,
+            names: vec![object_name(DELETED_NODES_TABLE)],
+            cascade: false,
+            restrict: false,
+            purge: false,
+            temporary: false,
+            table: None,
+        });
+    }
+    Ok(statements)
+}
+
+/// Converts `MATCH ... REMOVE n.a, n.b` to an `UPDATE` of each node's
+/// table setting the removed properties to `NULL`, for the nodes selected by
+/// id from the rows matching the pattern. Labels cannot be removed, since a
+/// node's label is the table it is stored in.
+pub fn cypher_remove_to_sql(
+    pattern: &str,
+    where_clause: &Option<Expr>,
+    items: &[CypherRemoveItem],
+    options: &ConversionOptions,
+) -> Result<Vec<Statement>, CypherConversionError> {
+    let graph = GraphPattern::parse(pattern)?;
+
+    let mut removed: Vec<(&Ident, Vec<Assignment>)> = vec![];
+    for item in items {
+        let (variable, property) = match item {
+            CypherRemoveItem::Property { variable, property } => (variable, property),
+            CypherRemoveItem::Label { .. } => {
+                return Err(CypherConversionError::UnsupportedPattern(format!(
+                    "REMOVE {item}: node labels cannot be removed"
+                )))
+            }
+        };
+        let column = options.target_dialect.column_ident(&property.value);
+        let assignment = Assignment {
+            target: AssignmentTarget::ColumnName(ObjectName(vec![ObjectNamePart::Identifier(
+                column,
+            )])),
+            value: Expr::Value(Value::Null.into()),
+        };
+        match removed.iter_mut().find(|(removed, _)| *removed == variable) {
+            Some((_, assignments)) => assignments.push(assignment),
+            None => removed.push((variable, vec![assignment])),
+        }
+    }
+
+    update_nodes(options, &graph, where_clause, removed)
+}
+
+/// Converts `MATCH ... SET n.a = 1, n += {b: 2, c: 3}` to an `UPDATE` of
+/// each node's table with one assignment per property set, for the nodes
+/// selected by id from the rows matching the pattern. A map merged with
+/// `+=` sets each of its keys, leaving the node's other properties as they
+/// are.
+pub fn cypher_set_to_sql(
+    pattern: &str,
+    where_clause: &Option<Expr>,
+    items: &[CypherSetItem],
+    options: &ConversionOptions,
+) -> Result<Vec<Statement>, CypherConversionError> {
+    let graph = GraphPattern::parse(pattern)?;
+    let mut context = ConversionContext::new(options);
+
+    let mut updated: Vec<(&Ident, Vec<Assignment>)> = vec![];
+    for item in items {
+        let (variable, properties) = match item {
+            CypherSetItem::Property {
+                variable,
+                property,
+                value,
+            } => (variable, vec![(property, value)]),
+            CypherSetItem::Merge {
+                variable,
+                properties: Expr::Dictionary(fields),
+            } => (
+                variable,
+                fields
+                    .iter()
+                    .map(|field| (&field.key, field.value.as_ref()))
+                    .collect(),
+            ),
+            CypherSetItem::Merge { .. } => {
+                return Err(CypherConversionError::UnsupportedPattern(format!(
+                    "SET {item}: only a map literal can be merged into a node"
+                )))
+            }
+        };
+        let mut assignments = vec![];
+        for (property, value) in properties {
+            let column = options.target_dialect.column_ident(&property.value);
+            assignments.push(Assignment {
+                target: AssignmentTarget::ColumnName(ObjectName(vec![ObjectNamePart::Identifier(
+                    column,
+                )])),
+                value: convert_expr(&mut context, value)?,
+            });
+        }
+        match updated.iter_mut().find(|(updated, _)| *updated == variable) {
+            Some((_, existing)) => existing.extend(assignments),
+            None => updated.push((variable, assignments)),
+        }
+    }
+
+    update_nodes(options, &graph, where_clause, updated)
+}
+
+/// Converts `MATCH ... MERGE (a)-[:KNOWS]->(b)` to an `INSERT` into the
+/// relationship's edge table of a row for each pair of endpoints matched by
+/// the pattern. Pairs already related are skipped with `ON CONFLICT` on the
+/// endpoint columns, or `INSERT IGNORE` on MySQL, so the edge table needs a
+/// unique key on those columns.
+pub fn cypher_merge_to_sql(
+    pattern: &str,
+    where_clause: &Option<Expr>,
+    merge: &str,
+    options: &ConversionOptions,
+) -> Result<Statement, CypherConversionError> {
+    let graph = GraphPattern::parse(pattern)?;
+    let merged = GraphPattern::parse(merge)?;
+    let unsupported = |reason: &str| {
+        CypherConversionError::UnsupportedPattern(format!("MERGE {merged}: {reason}"))
+    };
+
+    let (start, relationship, end) = match merged.paths.as_slice() {
+        [path] => match path.steps.as_slice() {
+            [(relationship, end)] => (&path.start, relationship, end),
+            _ => return Err(unsupported("only a single relationship can be merged")),
+        },
+        _ => return Err(unsupported("only a single relationship can be merged")),
+    };
+    if !relationship.properties.is_empty() {
+        return Err(unsupported("relationship properties cannot be merged"));
+    }
+    let mut endpoints = vec![];
+    for node in [start, end] {
+        match &node.variable {
+            Some(variable) if pattern_node(&graph, &Ident::new(variable)).is_ok() => {
+                endpoints.push(variable.as_str())
+            }
+            _ => return Err(unsupported("both nodes must be matched variables")),
+        }
+    }
+    let (source, target) = match relationship.direction {
+        RelationshipDirection::Outgoing => (endpoints[0], endpoints[1]),
+        RelationshipDirection::Incoming => (endpoints[1], endpoints[0]),
+        RelationshipDirection::Undirected => {
+            return Err(unsupported("the relationship needs a direction"))
+        }
+    };
+    let table_name = edge_table_name(options, relationship)
+        .ok_or_else(|| unsupported("the relationship needs a single type"))?;
+
+    let columns = vec![
+        Ident::new(EDGE_SOURCE_COLUMN),
+        Ident::new(EDGE_TARGET_COLUMN),
+    ];
+    let matched = matched_rows(
+        options,
+        &graph,
+        where_clause,
+        vec![
+            SelectItem::UnnamedExpr(column(source, NODE_ID_COLUMN)),
+            SelectItem::UnnamedExpr(column(target, NODE_ID_COLUMN)),
+        ],
+    )?;
+    let ignore = options.target_dialect == TargetDialect::MySql;
+    let on = (!ignore).then(|| {
+        OnInsert::OnConflict(OnConflict {
+            conflict_target: Some(ConflictTarget::Columns(columns.clone())),
+            action: OnConflictAction::DoNothing,
+        })
+    });
+
+    Ok(Statement::Insert(Insert {
+        or: None,
+        ignore,
+        into: true,
+        table: TableObject::TableName(object_name(&table_name)),
+        table_alias: None,
+        columns,
+        overwrite: false,
+        source: Some(Box::new(matched)),
+        assignments: vec![],
+        partitioned: None,
+        after_columns: vec![],
+        has_table_keyword: false,
+        on,
+        returning: None,
+        replace_into: false,
+        priority: None,
+        insert_alias: None,
+        settings: None,
+        format_clause: None,
+    }))
+}
+
+/// Returns the edge table that a relationship is written to: the table of
+/// its single type, or the default edge table when it has none.
+fn edge_table_name(
+    options: &ConversionOptions,
+    relationship: &RelationshipPattern,
+) -> Option<String> {
+    match relationship.types.as_slice() {
+        [relationship_type] => Some(relationship_type.clone()),
+        [] => options.default_edge_table.clone(),
+        _ => None,
+    }
+}
+
+/// Returns an `UPDATE` of the table of each node variable, making its
+/// assignments to the rows of the nodes matching a pattern and `WHERE`
+/// clause.
+fn update_nodes(
+    options: &ConversionOptions,
+    graph: &GraphPattern,
+    where_clause: &Option<Expr>,
+    updates: Vec<(&Ident, Vec<Assignment>)>,
+) -> Result<Vec<Statement>, CypherConversionError> {
+    updates
+        .into_iter()
+        .map(|(variable, assignments)| {
+            let node = pattern_node(graph, variable)?;
+            let matched = matched_node_ids(options, graph, where_clause, &variable.value)?;
+            Ok(Statement::Update(Update {
+                table: TableWithJoins {
+                    relation: table_factor(label_table(options, node_label(options, node)?), None),
+                    joins: vec![],
+                },
+                assignments,
+                from: None,
+                selection: Some(Expr::InSubquery {
+                    expr: Box::new(Expr::Identifier(Ident::new(NODE_ID_COLUMN))),
+                    subquery: Box::new(matched),
+                    negated: false,
+                }),
+                returning: None,
+                or: None,
+                limit: None,
+            }))
+        })
+        .collect()
+}
+
+/// Returns the node of a pattern bound to `variable`.
+fn pattern_node<'a>(
+    graph: &'a GraphPattern,
+    variable: &Ident,
+) -> Result<&'a NodePattern, CypherConversionError> {
+    graph
+        .paths
+        .iter()
+        .flat_map(|path| std::iter::once(&path.start).chain(path.steps.iter().map(|(_, n)| n)))
+        .find(|node| node.variable.as_deref() == Some(variable.value.as_str()))
+        .ok_or_else(|| {
+            CypherConversionError::UnsupportedPattern(format!("{variable} is not a node variable"))
+        })
+}
+
+/// Returns a query selecting the id of the node bound to `variable` in each
+/// row matching a pattern and `WHERE` clause.
+fn matched_node_ids(
+    options: &ConversionOptions,
+    graph: &GraphPattern,
+    where_clause: &Option<Expr>,
+    variable: &str,
+) -> Result<Query, CypherConversionError> {
+    matched_rows(
+        options,
+        graph,
+        where_clause,
+        vec![SelectItem::UnnamedExpr(column(variable, NODE_ID_COLUMN))],
+    )
+}
+
+/// Returns a query selecting `projection` from each row matching a pattern
+/// and `WHERE` clause.
+fn matched_rows(
+    options: &ConversionOptions,
+    graph: &GraphPattern,
+    where_clause: &Option<Expr>,
+    projection: Vec<SelectItem>,
+) -> Result<Query, CypherConversionError> {
+    let mut context = ConversionContext::new(options);
+    let PatternTables {
+        from,
+        predicates,
+        property_predicates,
+    } = graph_to_from(&mut context, graph)?;
+    let where_clause = where_clause
+        .as_ref()
+        .map(|expr| convert_expr(&mut context, expr))
+        .transpose()?;
+    Ok(create_query(SetExpr::Select(Box::new(create_select(
+        projection,
+        from,
+        selection(options, predicates, property_predicates, where_clause),
+    )))))
+}
+
+/// Splits a `RETURN` item of a deleted node into the node's variable and
+/// the item selecting it from the deleted row: `n` becomes `*` and `n.name`
+/// becomes `name`.
+fn deleted_row_item(item: &SelectItem) -> Result<(&str, SelectItem), CypherConversionError> {
+    let (expr, alias) = match item {
+        SelectItem::UnnamedExpr(expr) => (expr, None),
+        SelectItem::ExprWithAlias { expr, alias } => (expr, Some(alias)),
+        _ => {
+            return Err(CypherConversionError::UnsupportedPattern(format!(
+                "RETURN {item} after DELETE"
+            )))
+        }
+    };
+    let (variable, expr) = match expr {
+        Expr::Identifier(variable) if alias.is_none() => {
+            return Ok((
+                &variable.value,
+                SelectItem::Wildcard(WildcardAdditionalOptions::default()),
+            ))
+        }
+        Expr::CompoundIdentifier(parts) => match parts.as_slice() {
+            [variable, property] => (&variable.value, Expr::Identifier(property.clone())),
+            _ => {
+                return Err(CypherConversionError::UnsupportedPattern(format!(
+                    "RETURN {item} after DELETE"
+                )))
+            }
+        },
+        _ => {
+            return Err(CypherConversionError::UnsupportedPattern(format!(
+                "RETURN {item} after DELETE"
+            )))
+        }
+    };
+    Ok((
+        variable,
+        match alias {
+            Some(alias) => SelectItem::ExprWithAlias {
+                expr,
+                alias: alias.clone(),
+            },
+            None => SelectItem::UnnamedExpr(expr),
+        },
+    ))
+}
+
+fn delete_from(table_name: String, selection: Expr) -> Statement {
+    Statement::Delete(Delete {
+        tables: vec![],
+        from: FromTable::WithFromKeyword(vec![TableWithJoins {
+            relation: table_factor(table_name, None),
+            joins: vec![],
+        }]),
+        using: None,
+        selection: Some(selection),
+        returning: None,
+        order_by: vec![],
+        limit: None,
+    })
+}
+
+/// Converts a pattern, such as that of an `EXISTS { MATCH ... }` subquery,
+/// to a `SELECT` of `projection` correlated with the enclosing query through
+/// the variables it shares with it.
+fn convert_pattern_subquery(
+    context: &mut ConversionContext,
+    pattern: &str,
+    where_clause: &Option<Box<Expr>>,
+    projection: Expr,
+) -> Result<Query, CypherConversionError> {
+    let outer_variables = context.bound_variables.clone();
+
+    let graph = GraphPattern::parse(pattern)?;
+    let PatternTables {
+        from,
+        predicates,
+        property_predicates,
+    } = graph_to_from(context, &graph)?;
+    let where_clause = where_clause
+        .as_ref()
+        .map(|expr| convert_expr(context, expr))
+        .transpose()?;
+
+    context.bound_variables = outer_variables;
+
+    let select = create_select(
+        vec![SelectItem::UnnamedExpr(projection)],
+        from,
+        selection(
+            context.options,
+            predicates,
+            property_predicates,
+            where_clause,
+        ),
+    );
+    Ok(create_query(SetExpr::Select(Box::new(select))))
+}
+
+/// Converts the `MATCH` query of an `IN (MATCH ... RETURN ...)` subquery,
+/// correlated with the enclosing query through the variables it shares
+/// with it.
+fn convert_in_subquery(
+    context: &mut ConversionContext,
+    query: &Statement,
+) -> Result<Query, CypherConversionError> {
+    let outer_variables = context.bound_variables.clone();
+    let query = convert_match(context, query)?;
+    context.bound_variables = outer_variables;
+    Ok(query)
+}
+
+pub fn cypher_create_to_sql(pattern: &str) -> Result<Statement, CypherConversionError> {
+    cypher_create_to_sql_with_options(pattern, &ConversionOptions::default())
+}
+
+/// Converts a `CREATE` of a single node to an `INSERT` into its label's
+/// table, or of a single relationship between existing nodes to an `INSERT`
+/// into its edge table.
+pub fn cypher_create_to_sql_with_options(
+    pattern: &str,
+    options: &ConversionOptions,
+) -> Result<Statement, CypherConversionError> {
+    let mut graph = GraphPattern::parse(pattern)?;
+    if graph.paths.iter().any(|path| !path.steps.is_empty()) {
+        return cypher_create_relationship_to_sql(&graph, options);
+    }
+    let node = graph.paths.remove(0).start;
+    let label = node_label(options, &node)?;
+
+    let (columns, values) = match node.parameter {
+        Some(parameter) if options.parameter_map_placeholder => (
+            vec![],
+            vec![Expr::Value(Value::Placeholder(parameter).into())],
+        ),
+        Some(parameter) => {
+            return Err(CypherConversionError::UnsupportedPattern(format!(
+                "parameter map {parameter} has no known columns"
+            )))
+        }
+        None => {
+            if node.properties.is_empty() {
+                return Err(CypherConversionError::invalid_pattern(
+                    "No properties found in CREATE statement",
+                ));
+            }
+
+            let (columns, values): (Vec<Ident>, Vec<Expr>) = node
+                .properties
+                .into_iter()
+                .map(|(key, value)| (options.target_dialect.column_ident(&key.value), value))
+                .unzip();
+            let mut context = ConversionContext::new(options);
+            let values = columns
+                .iter()
+                .zip(values)
+                .map(|(column, value)| {
+                    let value = created_value(&mut context, column, value)?;
+                    Ok(
+                        match options
+                            .column_types
+                            .get(&(label.clone(), column.value.clone()))
+                        {
+                            Some(data_type) => Expr::Cast {
+                                kind: CastKind::Cast,
+                                expr: Box::new(value),
+                                data_type: data_type.clone(),
+                                format: None,
+                            },
+                            None => value,
+                        },
+                    )
+                })
+                .collect::<Result<_, CypherConversionError>>()?;
+            (columns, values)
+        }
+    };
+
+    Ok(insert_row(
+        options,
+        label_table(options, label),
+        columns,
+        values,
+    ))
+}
+
+/// Converts a `CREATE` of a single relationship, such as
+/// `CREATE (a {id: 1})-[:RATED {score: 5}]->(b {id: 2})`, to an `INSERT`
+/// into its edge table of the ids of its nodes along with its properties.
+/// The nodes must already exist, so they may only be given by their `id`.
+fn cypher_create_relationship_to_sql(
+    graph: &GraphPattern,
+    options: &ConversionOptions,
+) -> Result<Statement, CypherConversionError> {
+    let unsupported = |reason: &str| {
+        CypherConversionError::UnsupportedPattern(format!("CREATE {graph}: {reason}"))
+    };
+
+    let (start, relationship, end) = match graph.paths.as_slice() {
+        [path] => match path.steps.as_slice() {
+            [(relationship, end)] => (&path.start, relationship, end),
+            _ => return Err(unsupported("only a single relationship can be created")),
         },
-        joins: vec![],
+        _ => return Err(unsupported("only a single relationship can be created")),
+    };
+    let mut ids = vec![];
+    for node in [start, end] {
+        match node.properties.as_slice() {
+            [(key, id)] if key.value == NODE_ID_COLUMN && node.parameter.is_none() => {
+                ids.push(id.clone())
+            }
+            _ => return Err(unsupported("both nodes must be given by their id alone")),
+        }
+    }
+    match relationship.direction {
+        RelationshipDirection::Outgoing => {}
+        RelationshipDirection::Incoming => ids.reverse(),
+        RelationshipDirection::Undirected => {
+            return Err(unsupported("the relationship needs a direction"))
+        }
+    }
+    let table_name = edge_table_name(options, relationship)
+        .ok_or_else(|| unsupported("the relationship needs a single type"))?;
+
+    let mut columns = vec![
+        Ident::new(EDGE_SOURCE_COLUMN),
+        Ident::new(EDGE_TARGET_COLUMN),
+    ];
+    let mut values = ids;
+    let mut context = ConversionContext::new(options);
+    for (key, value) in &relationship.properties {
+        let column = options.target_dialect.column_ident(&key.value);
+        values.push(created_value(&mut context, &column, value.clone())?);
+        columns.push(column);
+    }
+
+    Ok(insert_row(options, table_name, columns, values))
+}
+
+/// Converts the value of a created property: function calls are converted,
+/// lists take the target's array syntax and maps become JSON with
+/// [`ConversionOptions::map_as_json`].
+fn created_value(
+    context: &mut ConversionContext,
+    column: &Ident,
+    value: Expr,
+) -> Result<Expr, CypherConversionError> {
+    let options = context.options;
+    Ok(match value {
+        Expr::BinaryOp { op, .. } => {
+            return Err(CypherConversionError::UnsupportedPattern(format!(
+                "{column} {op} ... cannot be created"
+            )))
+        }
+        Expr::Function(_) => convert_expr(context, &value)?,
+        Expr::Array(array) => Expr::Array(Array {
+            named: options.target_dialect.array_keyword(),
+            ..array
+        }),
+        Expr::Dictionary(_) if options.map_as_json => {
+            options.target_dialect.json(json_text(&value)?)
+        }
+        Expr::Dictionary(_) => {
+            return Err(CypherConversionError::UnsupportedPattern(format!(
+                "map value of {column} requires map_as_json"
+            )))
+        }
+        value => value,
+    })
+}
+
+/// Returns an `INSERT` of a single row into `table_name`, built as
+/// [`ConversionOptions::insert_style`] asks. Without `columns`, the values
+/// make up the whole row.
+fn insert_row(
+    options: &ConversionOptions,
+    table_name: String,
+    columns: Vec<Ident>,
+    values: Vec<Expr>,
+) -> Statement {
+    let source = match options.insert_style {
+        InsertStyle::Values => SetExpr::Values(Values {
+            explicit_row: false,
+            rows: vec![values],
+        }),
+        InsertStyle::Select => {
+            let projection = if columns.is_empty() {
+                values.into_iter().map(SelectItem::UnnamedExpr).collect()
+            } else {
+                columns
+                    .iter()
+                    .zip(values)
+                    .map(|(column, expr)| SelectItem::ExprWithAlias {
+                        expr,
+                        alias: column.clone(),
+                    })
+                    .collect()
+            };
+            SetExpr::Select(Box::new(create_select(projection, vec![], None)))
+        }
+    };
+
+    Statement::Insert(Insert {
+        or: None,
+        ignore: false,
+        into: true,
+        table: TableObject::TableName(object_name(&table_name)),
+        table_alias: None,
+        columns,
+        overwrite: false,
+        source: Some(Box::new(create_query(source))),
+        assignments: vec![],
+        partitioned: None,
+        after_columns: vec![],
+        has_table_keyword: false,
+        on: None,
+        returning: None,
+        replace_into: false,
+        priority: None,
+        insert_alias: None,
+        settings: None,
+        format_clause: None,
+    })
+}
+
+/// Converts `CREATE (n ...) WITH n MATCH ... RETURN ...` to a query whose
+/// `WITH` clause captures the inserted row, so the `MATCH` can join it.
+///
+/// The CTE is named after the bridged variable and holds the `INSERT` with
+/// `RETURNING *`, which requires a target that supports data-modifying
+/// CTEs (e.g. PostgreSQL). Only a single created node can be bridged, so
+/// `WITH *` bridges that node's variable.
+fn cypher_create_with_to_sql(
+    pattern: &str,
+    with: &[Ident],
+    query: &Statement,
+    options: &ConversionOptions,
+) -> Result<Statement, CypherConversionError> {
+    let created = GraphPattern::parse(pattern)?;
+    let variable = match (created.paths.as_slice(), with) {
+        ([path], []) if path.steps.is_empty() => path.start.variable.clone(),
+        ([path], [bridged]) if path.steps.is_empty() => path
+            .start
+            .variable
+            .clone()
+            .filter(|variable| *variable == bridged.value),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        CypherConversionError::UnsupportedPattern(
+            "WITH must pass on the single created node variable".to_string(),
+        )
+    })?;
+
+    let Statement::Insert(mut insert) = cypher_create_to_sql_with_options(pattern, options)? else {
+        unreachable!("cypher_create_to_sql_with_options always returns an INSERT");
     };
+    insert.returning = Some(vec![SelectItem::Wildcard(
+        WildcardAdditionalOptions::default(),
+    )]);
+
+    let mut context = ConversionContext::new(options);
+    context.cte_variables.push(variable.clone());
+    let mut query = convert_match(&mut context, query)?;
+    query.with = Some(With {
+        with_token: AttachedToken::empty(),
+        recursive: false,
+        cte_tables: vec![Cte {
+            alias: TableAlias {
+                name: Ident::new(variable),
+                columns: vec![],
+            },
+            query: Box::new(create_query(SetExpr::Insert(Statement::Insert(insert)))),
+            from: None,
+            materialized: None,
+            closing_paren_token: AttachedToken::empty(),
+        }],
+    });
+
+    Ok(Statement::Query(Box::new(query)))
+}
+
+/// Parse a simple value (string, number, boolean, function call, or a list or
+/// map of these)
+fn parse_simple_value(value: &str) -> Result<Expr, CypherConversionError> {
+    let value = value.trim();
+
+    if value.len() >= 2
+        && ((value.starts_with('\'') && value.ends_with('\''))
+            || (value.starts_with('"') && value.ends_with('"')))
+    {
+        let unquoted = &value[1..value.len() - 1];
+        return Ok(Expr::Value(
+            Value::SingleQuotedString(unquoted.to_string()).into(),
+        ));
+    }
+
+    // List
+    if let Some(items) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        return Ok(Expr::Array(Array {
+            elem: split_list_items(items)?
+                .iter()
+                .map(|item| parse_simple_value(item))
+                .collect::<Result<_, _>>()?,
+            named: false,
+        }));
+    }
+
+    // Map
+    if let Some(entries) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+        return Ok(Expr::Dictionary(
+            split_list_items(entries)?
+                .iter()
+                .map(|entry| {
+                    let (key, value) = entry.split_once(':').ok_or_else(|| {
+                        CypherConversionError::invalid_pattern(format!(
+                            "expected 'key: value' in map, found '{entry}'"
+                        ))
+                    })?;
+                    Ok(DictionaryField {
+                        key: Ident::new(key.trim()),
+                        value: Box::new(parse_simple_value(value)?),
+                    })
+                })
+                .collect::<Result<_, CypherConversionError>>()?,
+        ));
+    }
+
+    // Boolean
+    if value.eq_ignore_ascii_case("true") {
+        return Ok(Expr::Value(Value::Boolean(true).into()));
+    }
+    if value.eq_ignore_ascii_case("false") {
+        return Ok(Expr::Value(Value::Boolean(false).into()));
+    }
+    if value.eq_ignore_ascii_case("null") {
+        return Ok(Expr::Value(Value::Null.into()));
+    }
+
+    // Hex or octal integer, as in `0x1A`, which the tokenizer turns into
+    // `X'1A'`, or `0o17`. It is converted to decimal, since SQL dialects
+    // share no hex integer literal and MySQL reads `0x1A` as a binary string.
+    let (sign, unsigned) = match value.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", value),
+    };
+    let prefixed = |prefix: &str| {
+        unsigned
+            .get(..prefix.len())
+            .filter(|start| start.eq_ignore_ascii_case(prefix))
+            .map(|_| &unsigned[prefix.len()..])
+    };
+    let radix_digits = prefixed("X'")
+        .and_then(|digits| digits.strip_suffix('\''))
+        .or_else(|| prefixed("0x"))
+        .map(|digits| (16, digits))
+        .or_else(|| prefixed("0o").map(|digits| (8, digits)));
+    if let Some((radix, digits)) = radix_digits {
+        if !digits.is_empty() && digits.chars().all(|c| c.is_digit(radix)) {
+            let num = i64::from_str_radix(&format!("{sign}{digits}"), radix).map_err(|_| {
+                let prefix = if radix == 16 { "0x" } else { "0o" };
+                CypherConversionError::invalid_pattern(format!(
+                    "{sign}{prefix}{digits} does not fit in a 64-bit integer"
+                ))
+            })?;
+            return Ok(Expr::Value(number(num).into()));
+        }
+    }
+
+    // Number, possibly with digit separators or an exponent, as in
+    // `1_000_000` or `1.5e3`
+    if let Ok(num) = value.parse::<i64>() {
+        return Ok(Expr::Value(number(num).into()));
+    }
+    let digits = value.replace('_', "");
+    if value.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '_' | '.' | 'e' | 'E' | '+' | '-'))
+    {
+        if let Ok(num) = digits.parse::<i64>() {
+            return Ok(Expr::Value(number(num).into()));
+        }
+        // The text is only checked as a float, and kept as the literal
+        // itself, so that decimals keep their precision and trailing zeros,
+        // as in `0.100`.
+        if let (Ok(_), Ok(number)) = (digits.parse::<f64>(), digits.parse()) {
+            return Ok(Expr::Value(Value::Number(number, false).into()));
+        }
+    }
+
+    // Parameter
+    if value == "?" || value.starts_with('$') {
+        return Ok(Expr::Value(Value::Placeholder(value.to_string()).into()));
+    }
+
+    // Function call, as in `{ts: timestamp()}`
+    if let Some((name, _)) = value.split_once('(') {
+        if value.ends_with(')')
+            && !name.is_empty()
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        {
+            let expr = Parser::new(&GenericDialect {})
+                .try_with_sql(value)
+                .and_then(|mut parser| parser.parse_cypher_expr())?;
+            if let Expr::Function(_) = expr {
+                return Ok(expr);
+            }
+        }
+    }
+
+    // Property of another variable, as in `(b {manager_id: a.id})`
+    if let Some((variable, property)) = value.split_once('.') {
+        return Ok(Expr::CompoundIdentifier(vec![
+            Ident::new(variable.trim()),
+            Ident::new(property.trim()),
+        ]));
+    }
+
+    // Identifier (unquoted)
+    Ok(Expr::Identifier(Ident::new(value)))
+}
+
+/// Splits the text between the brackets of a list or map into the text of
+/// its items, ignoring commas inside nested values and string literals. A
+/// trailing comma, as in `[1, 2,]`, ends the last item rather than starting
+/// an empty one.
+fn split_list_items(items: &str) -> Result<Vec<String>, CypherConversionError> {
+    let tokens = Tokenizer::new(&GenericDialect {}, items)
+        .tokenize()
+        .map_err(|e| CypherConversionError::invalid_pattern(e.to_string()))?;
+
+    let mut result = vec![];
+    let mut item = String::new();
+    let mut depth = 0usize;
+    for token in tokens {
+        match token {
+            Token::Whitespace(_) => continue,
+            Token::Comma if depth == 0 => {
+                if item.is_empty() {
+                    return Err(CypherConversionError::invalid_pattern(format!(
+                        "empty item in '{items}'"
+                    )));
+                }
+                result.push(core::mem::take(&mut item));
+                continue;
+            }
+            Token::LParen | Token::LBracket | Token::LBrace => depth += 1,
+            Token::RParen | Token::RBracket | Token::RBrace => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        item.push_str(&token.to_string());
+    }
+    if !item.is_empty() {
+        result.push(item);
+    }
+    Ok(result)
+}
+
+/// Serializes a property value parsed by [`parse_simple_value`] as JSON.
+fn json_text(value: &Expr) -> Result<String, CypherConversionError> {
+    Ok(match value {
+        Expr::Value(value) => match &value.value {
+            Value::SingleQuotedString(s) => {
+                let mut json = String::from('"');
+                for c in s.chars() {
+                    match c {
+                        '"' => json.push_str("\\\""),
+                        '\\' => json.push_str("\\\\"),
+                        c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+                        c => json.push(c),
+                    }
+                }
+                json.push('"');
+                json
+            }
+            Value::Number(n, _) => n.to_string(),
+            Value::Boolean(b) => b.to_string(),
+            Value::Null => "null".to_string(),
+            _ => {
+                return Err(CypherConversionError::UnsupportedPattern(format!(
+                    "{value} has no JSON representation"
+                )))
+            }
+        },
+        Expr::Array(array) => format!(
+            "[{}]",
+            array
+                .elem
+                .iter()
+                .map(json_text)
+                .collect::<Result<Vec<_>, _>>()?
+                .join(",")
+        ),
+        Expr::Dictionary(fields) => format!(
+            "{{{}}}",
+            fields
+                .iter()
+                .map(|field| Ok(format!(
+                    "{}:{}",
+                    json_text(&Expr::Value(
+                        Value::SingleQuotedString(field.key.value.clone()).into()
+                    ))?,
+                    json_text(&field.value)?
+                )))
+                .collect::<Result<Vec<_>, CypherConversionError>>()?
+                .join(",")
+        ),
+        _ => {
+            return Err(CypherConversionError::UnsupportedPattern(format!(
+                "{value} has no JSON representation"
+            )))
+        }
+    })
+}
+
+/// Converts `RETURN` items to a projection. A whole node such as `RETURN n`
+/// becomes `*` when it is the only item, unless
+/// [`ConversionOptions::qualified_wildcard`] is set, and `n.*` otherwise so
+/// that `RETURN n, n.age` doesn't select every joined column.
+fn convert_return_items(
+    context: &mut ConversionContext,
+    return_items: &[SelectItem],
+) -> Result<Vec<SelectItem>, CypherConversionError> {
+    let single_item = return_items.len() == 1 && !context.options.qualified_wildcard;
+    return_items
+        .iter()
+        .map(|item| {
+            Ok(match item {
+                SelectItem::UnnamedExpr(Expr::Identifier(variable))
+                    if context.path_endpoints.contains_key(&variable.value) =>
+                {
+                    SelectItem::ExprWithAlias {
+                        expr: path_value(context, variable)?,
+                        alias: variable.clone(),
+                    }
+                }
+                SelectItem::UnnamedExpr(Expr::Identifier(_)) if single_item => {
+                    SelectItem::Wildcard(WildcardAdditionalOptions::default())
+                }
+                SelectItem::UnnamedExpr(Expr::Identifier(variable)) => {
+                    SelectItem::QualifiedWildcard(
+                        SelectItemQualifiedWildcardKind::ObjectName(ObjectName(vec![
+                            ObjectNamePart::Identifier(variable.clone()),
+                        ])),
+                        WildcardAdditionalOptions::default(),
+                    )
+                }
+                SelectItem::Wildcard(_) => item.clone(),
+                SelectItem::UnnamedExpr(expr) => {
+                    SelectItem::UnnamedExpr(convert_expr(context, expr)?)
+                }
+                SelectItem::ExprWithAlias { expr, alias } => SelectItem::ExprWithAlias {
+                    expr: convert_expr(context, expr)?,
+                    alias: alias.clone(),
+                },
+                _ => item.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Converts a named path as [`ConversionOptions::path_return`] asks.
+fn path_value(context: &ConversionContext, path: &Ident) -> Result<Expr, CypherConversionError> {
+    let (start_id, end_id) = &context.path_endpoints[&path.value];
+    match context.options.path_return {
+        PathReturn::Error => Err(CypherConversionError::UnsupportedPattern(format!(
+            "path {path} has no SQL type; set path_return to return the ids of its endpoints"
+        ))),
+        PathReturn::EndpointIds => {
+            let dialect = context.options.target_dialect;
+            let arrow = Expr::Value(Value::SingleQuotedString("->".to_string()).into());
+            Ok(dialect.concat(dialect.concat(start_id.clone(), arrow), end_id.clone()))
+        }
+    }
+}
+
+/// Cypher functions reading the clock when called without arguments, as
+/// in `date()`, and the standard SQL keyword, accepted by every target
+/// dialect, reading the same clock.
+const CLOCK_FUNCTIONS: &[(&str, &str)] = &[
+    ("timestamp", "CURRENT_TIMESTAMP"),
+    ("datetime", "CURRENT_TIMESTAMP"),
+    ("date", "CURRENT_DATE"),
+    ("time", "CURRENT_TIME"),
+    ("localdatetime", "LOCALTIMESTAMP"),
+    ("localtime", "LOCALTIME"),
+];
+
+/// Returns the SQL keyword standing for a call to a clock function.
+fn clock_keyword(function: &Function) -> Option<&'static str> {
+    if !matches!(&function.args, FunctionArguments::List(list) if list.args.is_empty()) {
+        return None;
+    }
+    let name = function.name.to_string();
+    CLOCK_FUNCTIONS
+        .iter()
+        .find(|(cypher, _)| name.eq_ignore_ascii_case(cypher))
+        .map(|(_, keyword)| *keyword)
+}
+
+/// Cypher aggregating functions, which group by the other `RETURN` items.
+const AGGREGATE_FUNCTIONS: &[&str] = &[
+    "avg",
+    "collect",
+    "count",
+    "max",
+    "min",
+    "percentileCont",
+    "percentileDisc",
+    "stDev",
+    "stDevP",
+    "sum",
+];
+
+/// Returns the `GROUP BY` implied by a `RETURN` that mixes aggregates with
+/// other items: Cypher groups by every item that is neither an aggregate nor
+/// a constant. `projection` holds the converted `return_items`.
+fn implicit_group_by(
+    return_items: &[SelectItem],
+    projection: &[SelectItem],
+) -> Result<Vec<Expr>, CypherConversionError> {
+    let item_expr = |item: &SelectItem| match item {
+        SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+            Some(expr.clone())
+        }
+        _ => None,
+    };
+    if !return_items
+        .iter()
+        .filter_map(item_expr)
+        .any(|expr| is_aggregate(&expr))
+    {
+        return Ok(vec![]);
+    }
+
+    let mut group_by = vec![];
+    for (item, converted) in return_items.iter().zip(projection) {
+        match (item_expr(item), item_expr(converted)) {
+            (Some(expr), _) if is_aggregate(&expr) || is_constant(&expr) => {}
+            (Some(_), Some(converted)) => group_by.push(converted),
+            _ => {
+                return Err(CypherConversionError::UnsupportedPattern(format!(
+                    "RETURN {item} alongside an aggregate"
+                )))
+            }
+        }
+    }
+    Ok(group_by)
+}
+
+fn is_aggregate(expr: &Expr) -> bool {
+    match expr {
+        // A window function such as `count(*) OVER ()` keeps one row per match
+        Expr::Function(function) if function.over.is_some() => false,
+        Expr::Function(function) => AGGREGATE_FUNCTIONS
+            .iter()
+            .any(|name| function.name.to_string().eq_ignore_ascii_case(name)),
+        Expr::BinaryOp { left, right, .. } => is_aggregate(left) || is_aggregate(right),
+        Expr::UnaryOp { expr, .. } | Expr::Nested(expr) | Expr::Cast { expr, .. } => {
+            is_aggregate(expr)
+        }
+        _ => false,
+    }
+}
+
+fn is_constant(expr: &Expr) -> bool {
+    match expr {
+        Expr::Value(_) => true,
+        Expr::BinaryOp { left, right, .. } => is_constant(left) && is_constant(right),
+        Expr::UnaryOp { expr, .. } | Expr::Nested(expr) | Expr::Cast { expr, .. } => {
+            is_constant(expr)
+        }
+        _ => false,
+    }
+}
+
+/// Rewrites a Cypher expression into its SQL equivalent.
+fn convert_expr(
+    context: &mut ConversionContext,
+    expr: &Expr,
+) -> Result<Expr, CypherConversionError> {
+    Ok(match expr {
+        Expr::BinaryOp {
+            op: BinaryOperator::And | BinaryOperator::Xor,
+            ..
+        } if mixes_and_xor(expr) => convert_expr(context, &cypher_xor_precedence(expr))?,
+        Expr::BinaryOp { left, op, right } => {
+            let left = convert_expr(context, left)?;
+            let right = convert_expr(context, right)?;
+            match op {
+                BinaryOperator::Eq if context.options.null_safe_equality => {
+                    Expr::IsNotDistinctFrom(Box::new(left), Box::new(right))
+                }
+                BinaryOperator::Plus if context.options.string_concat_plus => {
+                    context.options.target_dialect.concat(left, right)
+                }
+                BinaryOperator::And => range(left, right),
+                BinaryOperator::Lt
+                | BinaryOperator::LtEq
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq => comparison(left, op.clone(), right),
+                _ => binary_op(left, op.clone(), right),
+            }
+        }
+        Expr::UnaryOp { op, expr } => Expr::UnaryOp {
+            op: *op,
+            expr: Box::new(convert_expr(context, expr)?),
+        },
+        Expr::Identifier(variable) if context.path_endpoints.contains_key(&variable.value) => {
+            path_value(context, variable)?
+        }
+        Expr::Nested(expr) => Expr::Nested(Box::new(convert_expr(context, expr)?)),
+        Expr::IsNull(expr) => Expr::IsNull(Box::new(convert_expr(context, expr)?)),
+        Expr::IsNotNull(expr) => Expr::IsNotNull(Box::new(convert_expr(context, expr)?)),
+        Expr::InList {
+            expr,
+            list,
+            negated,
+        } => Expr::InList {
+            expr: Box::new(convert_expr(context, expr)?),
+            list: list
+                .iter()
+                .map(|item| convert_expr(context, item))
+                .collect::<Result<_, _>>()?,
+            negated: *negated,
+        },
+        Expr::CypherExists {
+            pattern,
+            where_clause,
+            negated,
+        } => Expr::Exists {
+            subquery: Box::new(convert_pattern_subquery(
+                context,
+                pattern,
+                where_clause,
+                Expr::Value(number(1).into()),
+            )?),
+            negated: *negated,
+        },
+        Expr::CypherInSubquery {
+            expr,
+            query,
+            negated,
+        } => Expr::InSubquery {
+            expr: Box::new(convert_expr(context, expr)?),
+            subquery: Box::new(convert_in_subquery(context, query)?),
+            negated: *negated,
+        },
+        Expr::Dictionary(fields) if context.options.map_as_json => {
+            let fields = fields
+                .iter()
+                .map(|field| {
+                    Ok((
+                        field.key.value.clone(),
+                        convert_expr(context, &field.value)?,
+                    ))
+                })
+                .collect::<Result<_, CypherConversionError>>()?;
+            context.options.target_dialect.json_object(fields)
+        }
+        Expr::Dictionary(_) => {
+            return Err(CypherConversionError::UnsupportedPattern(format!(
+                "map {expr} requires map_as_json"
+            )))
+        }
+        Expr::CypherInParameter {
+            expr,
+            parameter,
+            negated,
+        } => {
+            let expr = convert_expr(context, expr)?;
+            let parameter = convert_expr(context, parameter)?;
+            context
+                .options
+                .target_dialect
+                .in_parameter(expr, parameter, *negated)
+        }
+        Expr::CypherStringMatch {
+            expr,
+            operator,
+            pattern,
+        } => convert_string_match(context, expr, *operator, pattern)?,
+        Expr::CompoundFieldAccess { root, access_chain } => {
+            match (root.as_ref(), access_chain.as_slice()) {
+                (Expr::Function(function), [AccessExpr::Dot(Expr::Identifier(property))]) => {
+                    match relationship_endpoint(context, function)? {
+                        Some(alias) => {
+                            Expr::CompoundIdentifier(vec![Ident::new(alias), property.clone()])
+                        }
+                        None => expr.clone(),
+                    }
+                }
+                _ => expr.clone(),
+            }
+        }
+        Expr::Function(function) if function.name.to_string().eq_ignore_ascii_case("collect") => {
+            renamed_function(context, function, "ARRAY_AGG")?
+        }
+        Expr::Function(function)
+            if context.options.spatial
+                && matches!(
+                    function.name.to_string().to_ascii_lowercase().as_str(),
+                    "distance" | "point.distance"
+                ) =>
+        {
+            renamed_function(context, function, "ST_Distance")?
+        }
+        Expr::Function(function)
+            if context.options.spatial
+                && function.name.to_string().eq_ignore_ascii_case("point") =>
+        {
+            make_point(context, function)?
+        }
+        // Without `spatial`, a point is kept as written, map and all.
+        Expr::Function(function) if function.name.to_string().eq_ignore_ascii_case("point") => {
+            expr.clone()
+        }
+        Expr::Function(function) if function.name.to_string().eq_ignore_ascii_case("length") => {
+            let name = context.options.target_dialect.string_length_function();
+            renamed_function(context, function, name)?
+        }
+        Expr::Function(function) if function.name.to_string().eq_ignore_ascii_case("toString") => {
+            let data_type = context.options.target_dialect.string_type();
+            convert_cast_function(context, function, data_type)?
+        }
+        Expr::Function(function) if function.name.to_string().eq_ignore_ascii_case("id") => {
+            match variable_argument(function) {
+                Some(variable) => column(variable, NODE_ID_COLUMN),
+                None => {
+                    return Err(CypherConversionError::UnsupportedPattern(format!(
+                        "{function}: expected a node variable"
+                    )))
+                }
+            }
+        }
+        Expr::Function(function) if function.name.to_string().eq_ignore_ascii_case("type") => {
+            relationship_type(context, function)?
+        }
+        Expr::Function(function)
+            if function.name.to_string().eq_ignore_ascii_case("size")
+                && pattern_argument(function).is_some() =>
+        {
+            pattern_count(context, function)?
+        }
+        Expr::CypherPattern(pattern) => {
+            return Err(CypherConversionError::UnsupportedPattern(format!(
+                "pattern {pattern} can only be counted, with size()"
+            )))
+        }
+        Expr::Function(function) if function.name.to_string().eq_ignore_ascii_case("toFloat") => {
+            convert_cast_function(context, function, DataType::Double(ExactNumberInfo::None))?
+        }
+        // Cypher counts string positions from 0 and SQL from 1.
+        Expr::Substring {
+            expr,
+            substring_from,
+            substring_for,
+            special,
+            shorthand,
+        } => Expr::Substring {
+            expr: Box::new(convert_expr(context, expr)?),
+            substring_from: substring_from
+                .as_ref()
+                .map(|start| convert_expr(context, start).map(|start| Box::new(one_based(start))))
+                .transpose()?,
+            substring_for: substring_for
+                .as_ref()
+                .map(|length| convert_expr(context, length).map(Box::new))
+                .transpose()?,
+            special: *special,
+            shorthand: *shorthand,
+        },
+        Expr::Case {
+            case_token,
+            end_token,
+            operand,
+            conditions,
+            else_result,
+        } => Expr::Case {
+            case_token: case_token.clone(),
+            end_token: end_token.clone(),
+            operand: operand
+                .as_ref()
+                .map(|operand| convert_expr(context, operand).map(Box::new))
+                .transpose()?,
+            conditions: conditions
+                .iter()
+                .map(|when| {
+                    Ok(CaseWhen {
+                        condition: convert_expr(context, &when.condition)?,
+                        result: convert_expr(context, &when.result)?,
+                    })
+                })
+                .collect::<Result<_, CypherConversionError>>()?,
+            else_result: else_result
+                .as_ref()
+                .map(|result| convert_expr(context, result).map(Box::new))
+                .transpose()?,
+        },
+        Expr::Cast {
+            kind,
+            expr,
+            data_type,
+            format,
+        } => Expr::Cast {
+            kind: kind.clone(),
+            expr: Box::new(convert_expr(context, expr)?),
+            data_type: data_type.clone(),
+            format: format.clone(),
+        },
+        Expr::Between {
+            expr,
+            negated,
+            low,
+            high,
+        } => Expr::Between {
+            expr: Box::new(convert_expr(context, expr)?),
+            negated: *negated,
+            low: Box::new(convert_expr(context, low)?),
+            high: Box::new(convert_expr(context, high)?),
+        },
+        Expr::Like {
+            negated,
+            any,
+            expr,
+            pattern,
+            escape_char,
+        } => Expr::Like {
+            negated: *negated,
+            any: *any,
+            expr: Box::new(convert_expr(context, expr)?),
+            pattern: Box::new(convert_expr(context, pattern)?),
+            escape_char: escape_char.clone(),
+        },
+        Expr::ILike {
+            negated,
+            any,
+            expr,
+            pattern,
+            escape_char,
+        } => Expr::ILike {
+            negated: *negated,
+            any: *any,
+            expr: Box::new(convert_expr(context, expr)?),
+            pattern: Box::new(convert_expr(context, pattern)?),
+            escape_char: escape_char.clone(),
+        },
+        Expr::Function(function) => match clock_keyword(function) {
+            Some(keyword) => Expr::Function(Function {
+                name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new(keyword))]),
+                args: FunctionArguments::None,
+                ..function.clone()
+            }),
+            None => Expr::Function(convert_arguments(context, function)?),
+        },
+        _ => expr.clone(),
+    })
+}
+
+/// Converts `a STARTS WITH b`, `a ENDS WITH b` and `a CONTAINS b` to
+/// `a LIKE` a pattern around `b`.
+///
+/// The `LIKE` wildcards `%` and `_` of a literal `b` are escaped, along
+/// with the escape character `\`, under `ESCAPE '\'`; see
+/// [`ConversionWarning::EscapedLikeWildcards`]. Those of any other `b`,
+/// such as a parameter, are not, and so still match as wildcards.
+fn convert_string_match(
+    context: &mut ConversionContext,
+    expr: &Expr,
+    operator: CypherStringOperator,
+    pattern: &Expr,
+) -> Result<Expr, CypherConversionError> {
+    let (prefix, suffix) = match operator {
+        CypherStringOperator::StartsWith => ("", "%"),
+        CypherStringOperator::EndsWith => ("%", ""),
+        CypherStringOperator::Contains => ("%", "%"),
+    };
+    let literal = |text: &str| Expr::Value(Value::SingleQuotedString(text.to_string()).into());
+
+    let text = match pattern {
+        Expr::Value(value) => match &value.value {
+            Value::SingleQuotedString(text) => Some(text),
+            _ => None,
+        },
+        _ => None,
+    };
+    let (pattern, escape_char) = match text {
+        Some(text) if text.contains(['%', '_']) => {
+            let escaped = text
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            (
+                literal(&format!("{prefix}{escaped}{suffix}")),
+                Some(Value::SingleQuotedString("\\".to_string())),
+            )
+        }
+        Some(text) => (literal(&format!("{prefix}{text}{suffix}")), None),
+        None => {
+            let dialect = context.options.target_dialect;
+            let mut pattern = convert_expr(context, pattern)?;
+            if !prefix.is_empty() {
+                pattern = dialect.concat(literal(prefix), pattern);
+            }
+            if !suffix.is_empty() {
+                pattern = dialect.concat(pattern, literal(suffix));
+            }
+            (pattern, None)
+        }
+    };
+
+    Ok(Expr::Like {
+        negated: false,
+        any: false,
+        expr: Box::new(convert_expr(context, expr)?),
+        pattern: Box::new(pattern),
+        escape_char,
+    })
+}
+
+/// Converts a call to a function of another name, such as `collect(x)` to
+/// `ARRAY_AGG(x)`. Arguments are converted in place, keeping modifiers like
+/// `DISTINCT`.
+fn renamed_function(
+    context: &mut ConversionContext,
+    function: &Function,
+    name: &str,
+) -> Result<Expr, CypherConversionError> {
+    let mut function = convert_arguments(context, function)?;
+    function.name = ObjectName(vec![ObjectNamePart::Identifier(Ident::new(name))]);
+    Ok(Expr::Function(function))
+}
+
+/// Converts the arguments of a function call in place, such as the
+/// `timestamp()` of `coalesce(n.ts, timestamp())`.
+fn convert_arguments(
+    context: &mut ConversionContext,
+    function: &Function,
+) -> Result<Function, CypherConversionError> {
+    let mut function = function.clone();
+    if let FunctionArguments::List(list) = &mut function.args {
+        for arg in &mut list.args {
+            if let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg {
+                *expr = convert_expr(context, expr)?;
+            }
+        }
+    }
+    Ok(function)
+}
+
+/// Converts `point({x: 1, y: 2, z: 3})`, or its geographic form with
+/// `longitude`, `latitude` and `height`, to `ST_MakePoint(1, 2, 3)`.
+fn make_point(
+    context: &mut ConversionContext,
+    point: &Function,
+) -> Result<Expr, CypherConversionError> {
+    let unsupported = || {
+        CypherConversionError::UnsupportedPattern(format!(
+            "{point}: a point needs x and y, or longitude and latitude"
+        ))
+    };
+    let fields = match &point.args {
+        FunctionArguments::List(list) => match list.args.as_slice() {
+            [FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Dictionary(fields)))] => fields,
+            _ => return Err(unsupported()),
+        },
+        _ => return Err(unsupported()),
+    };
+    let mut coordinates: [Option<Expr>; 3] = [None, None, None];
+    for field in fields {
+        let axis = match field.key.value.to_ascii_lowercase().as_str() {
+            "x" | "longitude" => 0,
+            "y" | "latitude" => 1,
+            "z" | "height" => 2,
+            _ => return Err(unsupported()),
+        };
+        coordinates[axis] = Some(convert_expr(context, &field.value)?);
+    }
+    match coordinates {
+        [Some(x), Some(y), z] => Ok(function(
+            "ST_MakePoint",
+            [x, y].into_iter().chain(z).collect(),
+        )),
+        _ => Err(unsupported()),
+    }
+}
+
+/// Converts a 0-based position to the 1-based position of SQL string
+/// functions: a literal is incremented, and any other position is added 1.
+fn one_based(position: Expr) -> Expr {
+    if let Expr::Value(value) = &position {
+        if let Value::Number(literal, _) = &value.value {
+            let next = literal.to_string().parse::<i64>().ok();
+            if let Some(next) = next.and_then(|position| position.checked_add(1)) {
+                return Expr::Value(number(next).into());
+            }
+        }
+    }
+    binary_op(
+        position,
+        BinaryOperator::Plus,
+        Expr::Value(number(1).into()),
+    )
+}
+
+/// Builds the number literal `n`, whichever type backs `Value::Number`.
+fn number(n: i64) -> Value {
+    Value::Number(n.to_string().parse().unwrap(), false)
+}
+
+/// Converts a single argument conversion function such as `toString(x)`
+/// to `CAST(x AS <data_type>)`. The cast delimits its argument, so e.g.
+/// `toFloat(a) / b` only converts `a`.
+fn convert_cast_function(
+    context: &mut ConversionContext,
+    function: &Function,
+    data_type: DataType,
+) -> Result<Expr, CypherConversionError> {
+    match &function.args {
+        FunctionArguments::List(list) => match list.args.as_slice() {
+            [FunctionArg::Unnamed(FunctionArgExpr::Expr(arg))] => Ok(Expr::Cast {
+                kind: CastKind::Cast,
+                expr: Box::new(convert_expr(context, arg)?),
+                data_type,
+                format: None,
+            }),
+            _ => Err(CypherConversionError::UnsupportedPattern(format!(
+                "{} takes a single argument",
+                function.name
+            ))),
+        },
+        _ => Ok(Expr::Function(function.clone())),
+    }
+}
+
+/// Resolves `startNode(r)` and `endNode(r)` to the alias of the node the
+/// relationship `r` starts from or points to. Returns `None` for any other
+/// function.
+fn relationship_endpoint(
+    context: &ConversionContext,
+    function: &Function,
+) -> Result<Option<String>, CypherConversionError> {
+    let name = function.name.to_string();
+    let start = if name.eq_ignore_ascii_case("startNode") {
+        true
+    } else if name.eq_ignore_ascii_case("endNode") {
+        false
+    } else {
+        return Ok(None);
+    };
+
+    let Some(variable) = variable_argument(function) else {
+        return Ok(None);
+    };
+
+    match context.relationship_endpoints.get(variable) {
+        Some(Some((start_alias, end_alias))) => Ok(Some(if start {
+            start_alias.clone()
+        } else {
+            end_alias.clone()
+        })),
+        Some(None) => Err(CypherConversionError::UnsupportedPattern(format!(
+            "{name}({variable}) of an undirected relationship"
+        ))),
+        None => Err(CypherConversionError::UnsupportedPattern(format!(
+            "{variable} is not a relationship variable"
+        ))),
+    }
+}
+
+/// Converts `type(r)` to the literal name of the type of the relationship
+/// `r`, such as `'KNOWS'`.
+fn relationship_type(
+    context: &ConversionContext,
+    function: &Function,
+) -> Result<Expr, CypherConversionError> {
+    let variable = variable_argument(function).ok_or_else(|| {
+        CypherConversionError::UnsupportedPattern(format!(
+            "{function}: expected a relationship variable"
+        ))
+    })?;
+    match context.relationship_types.get(variable).map(Vec::as_slice) {
+        Some([relationship_type]) => Ok(Expr::Value(
+            Value::SingleQuotedString(relationship_type.clone()).into(),
+        )),
+        Some(_) => Err(CypherConversionError::UnsupportedPattern(format!(
+            "{function} of a relationship without a single type"
+        ))),
+        None => Err(CypherConversionError::UnsupportedPattern(format!(
+            "{variable} is not a relationship variable"
+        ))),
+    }
+}
+
+/// Returns the variable passed as the only argument of a function, as in
+/// `startNode(r)`.
+fn variable_argument(function: &Function) -> Option<&String> {
+    match &function.args {
+        FunctionArguments::List(list) => match list.args.as_slice() {
+            [FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Identifier(variable)))] => {
+                Some(&variable.value)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Converts `size((n)-[:KNOWS]->())` to a count of the matches of its
+/// pattern: `(SELECT COUNT(*) FROM KNOWS AS _r1 WHERE _r1.source_id = n.id)`.
+fn pattern_count(
+    context: &mut ConversionContext,
+    function: &Function,
+) -> Result<Expr, CypherConversionError> {
+    let pattern = pattern_argument(function).ok_or_else(|| {
+        CypherConversionError::UnsupportedPattern(format!("{function}: expected a pattern"))
+    })?;
+    Ok(Expr::Subquery(Box::new(convert_pattern_subquery(
+        context,
+        pattern,
+        &None,
+        count_rows(),
+    )?)))
+}
+
+/// Returns the pattern passed as the only argument of a function, as in
+/// `size((n)-[:KNOWS]->())`.
+fn pattern_argument(function: &Function) -> Option<&str> {
+    match &function.args {
+        FunctionArguments::List(list) => match list.args.as_slice() {
+            [FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::CypherPattern(pattern)))] => {
+                Some(pattern)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// A parsed Cypher pattern: one or more comma separated paths.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphPattern {
+    pub paths: Vec<PathPattern>,
+}
+
+/// A node followed by zero or more relationship hops, e.g.
+/// `(a:Person)-[:KNOWS]->(b:Person)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathPattern {
+    /// `p` in `p = (a)-[:KNOWS]->(b)`
+    pub variable: Option<String>,
+    pub start: NodePattern,
+    pub steps: Vec<(RelationshipPattern, NodePattern)>,
+}
+
+/// A node pattern such as `(n:Person {name: 'Alice'})`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodePattern {
+    pub variable: Option<String>,
+    pub labels: Vec<String>,
+    pub properties: Vec<(Ident, Expr)>,
+    /// A parameter standing in for the whole property map, as in
+    /// `(n:Person $props)`.
+    pub parameter: Option<String>,
+}
+
+/// A relationship pattern such as `-[r:KNOWS]->`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationshipPattern {
+    pub variable: Option<String>,
+    pub types: Vec<String>,
+    pub properties: Vec<(Ident, Expr)>,
+    pub direction: RelationshipDirection,
+}
+
+/// Direction of a relationship pattern relative to the node on its left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelationshipDirection {
+    /// `(a)-[]->(b)`
+    Outgoing,
+    /// `(a)<-[]-(b)`
+    Incoming,
+    /// `(a)-[]-(b)`
+    Undirected,
+}
+
+impl GraphPattern {
+    /// Parses the pattern text captured by the parser, e.g.
+    /// `( a : Person ) - [ : KNOWS ] -> ( b : Person )`.
+    pub fn parse(pattern: &str) -> Result<Self, CypherConversionError> {
+        // Character offset at which each line of the pattern starts, to turn
+        // token locations into offsets.
+        let line_starts: Vec<usize> = core::iter::once(0)
+            .chain(
+                pattern
+                    .chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '\n')
+                    .map(|(i, _)| i + 1),
+            )
+            .collect();
+        let offset = |location: Location| {
+            let line = (location.line as usize).saturating_sub(1);
+            line_starts.get(line).copied().unwrap_or(0)
+                + (location.column as usize).saturating_sub(1)
+        };
+
+        let (tokens, positions) = Tokenizer::new(&GenericDialect {}, pattern)
+            .tokenize_with_location()
+            .map_err(|e| CypherConversionError::InvalidPattern {
+                message: e.message,
+                position: Some(offset(e.location)),
+            })?
+            .into_iter()
+            .filter(|token| !matches!(token.token, Token::Whitespace(_)))
+            .map(|token| (token.token, offset(token.span.start)))
+            .unzip();
+        let mut parser = PatternParser {
+            tokens,
+            positions,
+            end: pattern.chars().count(),
+            index: 0,
+        };
+
+        let mut paths = vec![parser.parse_path()?];
+        while parser.consume(&Token::Comma) {
+            paths.push(parser.parse_path()?);
+        }
+        if let Some(token) = parser.peek() {
+            // `WITH` is valid Cypher, most often used to filter groups as
+            // SQL's `HAVING` does, but its clauses are not converted.
+            if matches!(token, Token::Word(w) if w.keyword == Keyword::WITH) {
+                return Err(CypherConversionError::UnsupportedPattern(
+                    "WITH between MATCH and RETURN".to_string(),
+                ));
+            }
+            return Err(parser.error(parser.index, format!("unexpected '{token}' after pattern")));
+        }
+
+        Ok(GraphPattern { paths })
+    }
+}
+
+impl fmt::Display for GraphPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", display_comma_separated(&self.paths))
+    }
+}
+
+impl fmt::Display for PathPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(variable) = &self.variable {
+            write!(f, "{variable} = ")?;
+        }
+        write!(f, "{}", self.start)?;
+        for (relationship, node) in &self.steps {
+            write!(f, "{relationship}{node}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for NodePattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("(")?;
+        if let Some(variable) = &self.variable {
+            f.write_str(variable)?;
+        }
+        for label in &self.labels {
+            write!(f, ":{label}")?;
+        }
+        let separator = if self.variable.is_some() || !self.labels.is_empty() {
+            " "
+        } else {
+            ""
+        };
+        if let Some(parameter) = &self.parameter {
+            write!(f, "{separator}{parameter}")?;
+        } else if !self.properties.is_empty() {
+            write!(f, "{separator}")?;
+            display_properties(f, &self.properties)?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl fmt::Display for RelationshipPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.direction == RelationshipDirection::Incoming {
+            f.write_str("<")?;
+        }
+        // Brackets are always written: a bare `--` would start a SQL comment.
+        f.write_str("-[")?;
+        if let Some(variable) = &self.variable {
+            f.write_str(variable)?;
+        }
+        if !self.types.is_empty() {
+            write!(f, ":{}", self.types.join("|"))?;
+        }
+        if !self.properties.is_empty() {
+            if self.variable.is_some() || !self.types.is_empty() {
+                f.write_str(" ")?;
+            }
+            display_properties(f, &self.properties)?;
+        }
+        f.write_str("]")?;
+        f.write_str("-")?;
+        if self.direction == RelationshipDirection::Outgoing {
+            f.write_str(">")?;
+        }
+        Ok(())
+    }
+}
+
+fn display_properties(f: &mut fmt::Formatter, properties: &[(Ident, Expr)]) -> fmt::Result {
+    f.write_str("{")?;
+    for (i, (key, value)) in properties.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        match value {
+            Expr::BinaryOp { op, right, .. } => write!(f, "{key} {op} {right}")?,
+            value => write!(f, "{key}: {value}")?,
+        }
+    }
+    f.write_str("}")
+}
+
+/// Recursive descent parser over the tokens of a pattern.
+struct PatternParser {
+    tokens: Vec<Token>,
+    /// Character offset of each token in the pattern text.
+    positions: Vec<usize>,
+    /// Length of the pattern text, in characters.
+    end: usize,
+    index: usize,
+}
+
+impl PatternParser {
+    /// Error located at the token at `index`, or at the end of the pattern
+    /// when there is no such token.
+    fn error(&self, index: usize, message: String) -> CypherConversionError {
+        CypherConversionError::InvalidPattern {
+            message,
+            position: Some(self.positions.get(index).copied().unwrap_or(self.end)),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        token
+    }
+
+    fn consume(&mut self, expected: &Token) -> bool {
+        if self.peek() == Some(expected) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), CypherConversionError> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(self.error(
+                self.index - 1,
+                format!("expected '{expected}', found '{token}'"),
+            )),
+            None => Err(self.error(
+                self.index - 1,
+                format!("expected '{expected}', found end of pattern"),
+            )),
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, CypherConversionError> {
+        match self.next() {
+            Some(Token::Word(word)) => Ok(word.value),
+            Some(token) => {
+                Err(self.error(self.index - 1, format!("expected a name, found '{token}'")))
+            }
+            None => Err(self.error(
+                self.index - 1,
+                "expected a name, found end of pattern".to_string(),
+            )),
+        }
+    }
+
+    fn parse_optional_variable(&mut self) -> Option<String> {
+        match self.peek() {
+            Some(Token::Word(word)) => {
+                let variable = word.value.clone();
+                self.index += 1;
+                Some(variable)
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_path(&mut self) -> Result<PathPattern, CypherConversionError> {
+        let variable = match (self.peek(), self.tokens.get(self.index + 1)) {
+            (Some(Token::Word(word)), Some(Token::Eq)) => {
+                let variable = word.value.clone();
+                self.index += 2;
+                Some(variable)
+            }
+            _ => None,
+        };
+        let start = self.parse_node()?;
+        let mut steps = vec![];
+        while matches!(self.peek(), Some(Token::Minus) | Some(Token::Lt)) {
+            let relationship = self.parse_relationship()?;
+            let node = self.parse_node()?;
+            steps.push((relationship, node));
+        }
+        Ok(PathPattern {
+            variable,
+            start,
+            steps,
+        })
+    }
+
+    fn parse_node(&mut self) -> Result<NodePattern, CypherConversionError> {
+        self.expect(Token::LParen)?;
+        let variable = self.parse_optional_variable();
+        let mut labels = vec![];
+        while self.consume(&Token::Colon) {
+            labels.push(self.parse_name()?);
+        }
+        let parameter = match self.peek() {
+            Some(Token::Placeholder(parameter)) => {
+                let parameter = parameter.clone();
+                self.index += 1;
+                Some(parameter)
+            }
+            _ => None,
+        };
+        let properties = self.parse_optional_properties()?;
+        self.expect(Token::RParen)?;
+
+        Ok(NodePattern {
+            variable,
+            labels,
+            properties,
+            parameter,
+        })
+    }
+
+    fn parse_relationship(&mut self) -> Result<RelationshipPattern, CypherConversionError> {
+        let start = self.index;
+        let incoming = self.consume(&Token::Lt);
+        self.expect(Token::Minus)?;
+
+        let mut variable = None;
+        let mut types = vec![];
+        let mut properties = vec![];
+        if self.consume(&Token::LBracket) {
+            variable = self.parse_optional_variable();
+            if self.consume(&Token::Colon) {
+                types.push(self.parse_name()?);
+                while self.consume(&Token::Pipe) {
+                    types.push(self.parse_name()?);
+                }
+            }
+            properties = self.parse_optional_properties()?;
+            self.expect(Token::RBracket)?;
+        }
+
+        let outgoing = if self.consume(&Token::Arrow) {
+            true
+        } else {
+            self.expect(Token::Minus)?;
+            false
+        };
+        let direction = match (incoming, outgoing) {
+            (false, true) => RelationshipDirection::Outgoing,
+            (true, false) => RelationshipDirection::Incoming,
+            (false, false) => RelationshipDirection::Undirected,
+            (true, true) => {
+                return Err(self.error(
+                    start,
+                    "relationship cannot point in both directions".to_string(),
+                ))
+            }
+        };
+
+        Ok(RelationshipPattern {
+            variable,
+            types,
+            properties,
+            direction,
+        })
+    }
+
+    fn parse_optional_properties(&mut self) -> Result<Vec<(Ident, Expr)>, CypherConversionError> {
+        let mut properties = vec![];
+        if !self.consume(&Token::LBrace) {
+            return Ok(properties);
+        }
+        while !self.consume(&Token::RBrace) {
+            let key = Ident::new(self.parse_name()?);
+            // `{status <> 'deleted'}` matches the rows where the property
+            // differs from the value, kept as that comparison.
+            if self.consume(&Token::Neq) {
+                let value = parse_simple_value(&self.parse_property_value()?)?;
+                let inequality =
+                    binary_op(Expr::Identifier(key.clone()), BinaryOperator::NotEq, value);
+                properties.push((key, inequality));
+            } else {
+                self.expect(Token::Colon)?;
+                let value = self.parse_property_value()?;
+                properties.push((key, parse_simple_value(&value)?));
+            }
+            // A comma may also trail the last property, as in `{a: 1,}`.
+            if !self.consume(&Token::Comma) {
+                self.expect(Token::RBrace)?;
+                break;
+            }
+        }
+        Ok(properties)
+    }
+
+    /// Collects the text of a property value, stopping at the `,` or `}`
+    /// that ends it.
+    fn parse_property_value(&mut self) -> Result<String, CypherConversionError> {
+        let mut value = String::new();
+        let mut depth = 0usize;
+        loop {
+            match self.peek() {
+                None => return Err(self.error(self.index, "unterminated property map".to_string())),
+                Some(Token::Comma | Token::RBrace) if depth == 0 => break,
+                Some(Token::LParen | Token::LBracket | Token::LBrace) => depth += 1,
+                Some(Token::RParen | Token::RBracket | Token::RBrace) => {
+                    depth = depth.checked_sub(1).ok_or_else(|| {
+                        self.error(
+                            self.index,
+                            "unbalanced brackets in property value".to_string(),
+                        )
+                    })?
+                }
+                Some(_) => {}
+            }
+            if let Some(token) = self.next() {
+                value.push_str(&token.to_string());
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// State shared by the helpers converting a single statement.
+struct ConversionContext<'a> {
+    options: &'a ConversionOptions,
+    aliases: AliasGenerator,
+    /// Variables bound to a CTE by an earlier clause, e.g. `CREATE ... WITH n`.
+    cte_variables: Vec<String>,
+    /// Aliases of the start and end node of each relationship variable,
+    /// or `None` for undirected relationships.
+    relationship_endpoints: HashMap<String, Option<(String, String)>>,
+    /// Types of each relationship variable, as in `[r:KNOWS]`.
+    relationship_types: HashMap<String, Vec<String>>,
+    /// Ids of the first and last node of each named path.
+    path_endpoints: HashMap<String, (Expr, Expr)>,
+    /// Node variables that already have a table alias in scope.
+    bound_variables: Vec<String>,
+}
+
+impl<'a> ConversionContext<'a> {
+    fn new(options: &'a ConversionOptions) -> Self {
+        Self {
+            options,
+            aliases: AliasGenerator::default(),
+            cte_variables: vec![],
+            relationship_endpoints: HashMap::new(),
+            relationship_types: HashMap::new(),
+            path_endpoints: HashMap::new(),
+            bound_variables: vec![],
+        }
+    }
+
+    fn is_bound(&self, node: &NodePattern) -> bool {
+        node.variable
+            .as_ref()
+            .is_some_and(|variable| self.bound_variables.contains(variable))
+    }
+
+    fn bind(&mut self, node: &NodePattern) {
+        if let Some(variable) = &node.variable {
+            self.bound_variables.push(variable.clone());
+        }
+    }
+}
+
+/// Hands out aliases for pattern elements, generating one for elements
+/// without a variable that still need to be referenced in a join.
+#[derive(Default)]
+struct AliasGenerator {
+    generated: usize,
+}
+
+impl AliasGenerator {
+    fn alias(&mut self, variable: &Option<String>, prefix: &str) -> String {
+        match variable {
+            Some(variable) => variable.clone(),
+            None => {
+                self.generated += 1;
+                format!("{prefix}{}", self.generated)
+            }
+        }
+    }
+}
+
+/// Converts a pattern to the tables of its paths and the predicates on
+/// their rows.
+///
+/// Nodes whose variable is already bound, by an enclosing query or earlier
+/// in the pattern, reuse the existing alias rather than joining their table
+/// again. Anonymous nodes without a label, such as the `()` in
+/// `()-[:KNOWS]->(n)`, are not joined at all, wherever they appear in a
+/// path: the edge row identifies them.
+fn graph_to_from(
+    context: &mut ConversionContext,
+    graph: &GraphPattern,
+) -> Result<PatternTables, CypherConversionError> {
+    let mut from = vec![];
+    let mut predicates = vec![];
+    let mut property_predicates = vec![];
+    for path in &graph.paths {
+        let mut tables = JoinedTables::default();
+        path_to_tables(
+            context,
+            path,
+            &mut tables,
+            &mut predicates,
+            &mut property_predicates,
+        )?;
+        if let Some(relation) = tables.relation {
+            from.push(TableWithJoins {
+                relation,
+                joins: tables.joins,
+            });
+        }
+    }
+    Ok(PatternTables {
+        from,
+        predicates,
+        property_predicates,
+    })
+}
+
+/// Tables of a pattern along with the predicates on their rows.
+#[derive(Default)]
+struct PatternTables {
+    from: Vec<TableWithJoins>,
+    /// Join conditions that could not be attached to a join.
+    predicates: Vec<Expr>,
+    /// Predicates of the pattern's inline property maps.
+    property_predicates: Vec<Expr>,
+}
+
+/// Tables of a single path, joined in order.
+#[derive(Default)]
+struct JoinedTables {
+    relation: Option<TableFactor>,
+    joins: Vec<Join>,
+}
+
+impl JoinedTables {
+    /// Joins `table` on `on`, or, when it is the first table of the path,
+    /// makes it the relation and leaves `on` as a predicate.
+    fn join(&mut self, table: TableFactor, on: Expr, predicates: &mut Vec<Expr>) {
+        if self.relation.is_none() {
+            self.relation = Some(table);
+            predicates.push(on);
+        } else {
+            self.joins.push(inner_join(table, on));
+        }
+    }
+}
+
+/// Converts a path to its start node's table joined, hop by hop, with the
+/// edge table of each relationship and the table of the node it leads to.
+///
+/// The joins follow the path from left to right, so `(a)-[:R1]->(b)-[:R2]->(c)`
+/// always becomes `a JOIN R1 JOIN b JOIN R2 JOIN c`: a left-deep join tree
+/// in which each join's condition refers only to tables joined before it.
+fn path_to_tables(
+    context: &mut ConversionContext,
+    path: &PathPattern,
+    tables: &mut JoinedTables,
+    predicates: &mut Vec<Expr>,
+    property_predicates: &mut Vec<Expr>,
+) -> Result<(), CypherConversionError> {
+    if path.steps.is_empty() {
+        // A lone node only needs an alias to qualify its properties.
+        let alias = if path.start.properties.is_empty() {
+            path.start.variable.clone()
+        } else {
+            Some(context.aliases.alias(&path.start.variable, "_n"))
+        };
+        if let Some(alias) = &alias {
+            push_property_predicates(
+                context.options,
+                Some(alias),
+                &path.start.properties,
+                property_predicates,
+            )?;
+        }
+        if let Some(variable) = &path.variable {
+            let id = match &alias {
+                Some(alias) => column(alias, NODE_ID_COLUMN),
+                None => Expr::Identifier(Ident::new(NODE_ID_COLUMN)),
+            };
+            context
+                .path_endpoints
+                .insert(variable.clone(), (id.clone(), id));
+        }
+        if !context.is_bound(&path.start) {
+            tables.relation = Some(aliased_table(
+                context.options,
+                node_table(context, &path.start)?,
+                alias,
+            ));
+            context.bind(&path.start);
+        }
+        return Ok(());
+    }
+
+    // The id of the node the next relationship starts from: the id column
+    // of its row, or, for an anonymous node that isn't joined, the edge
+    // column identifying it. An anonymous start node constrains nothing.
+    let mut left = context.aliases.alias(&path.start.variable, "_n");
+    let mut left_id = None;
+    if !is_anonymous(&path.start) {
+        push_property_predicates(
+            context.options,
+            Some(&left),
+            &path.start.properties,
+            property_predicates,
+        )?;
+        if !context.is_bound(&path.start) {
+            tables.relation = Some(aliased_table(
+                context.options,
+                node_table(context, &path.start)?,
+                Some(left.clone()),
+            ));
+            context.bind(&path.start);
+        }
+        left_id = Some(column(&left, NODE_ID_COLUMN));
+    }
+    let mut start_id = left_id.clone();
+
+    let mut steps = path.steps.iter().peekable();
+    while let Some((relationship, node)) = steps.next() {
+        let edge = context.aliases.alias(&relationship.variable, "_r");
+        let right = context.aliases.alias(&node.variable, "_n");
+        push_property_predicates(
+            context.options,
+            Some(&edge),
+            &relationship.properties,
+            property_predicates,
+        )?;
+        push_property_predicates(
+            context.options,
+            Some(&right),
+            &node.properties,
+            property_predicates,
+        )?;
+        if left_id.is_none() {
+            // The anonymous start node is the other end of the first edge.
+            start_id = match relationship.direction {
+                RelationshipDirection::Outgoing => Some(column(&edge, EDGE_SOURCE_COLUMN)),
+                RelationshipDirection::Incoming => Some(column(&edge, EDGE_TARGET_COLUMN)),
+                RelationshipDirection::Undirected => None,
+            };
+        }
+
+        if let Some(variable) = &relationship.variable {
+            let endpoints = match relationship.direction {
+                RelationshipDirection::Outgoing => Some((left.clone(), right.clone())),
+                RelationshipDirection::Incoming => Some((right.clone(), left.clone())),
+                RelationshipDirection::Undirected => None,
+            };
+            context
+                .relationship_endpoints
+                .insert(variable.clone(), endpoints);
+            context
+                .relationship_types
+                .insert(variable.clone(), relationship.types.clone());
+        }
+
+        let left_is = |end: &str| {
+            left_id
+                .clone()
+                .map(|left_id| eq(column(&edge, end), left_id))
+        };
+        let right_is = |end: &str| eq(column(&right, NODE_ID_COLUMN), column(&edge, end));
+        let (edge_on, node_on) = match relationship.direction {
+            RelationshipDirection::Outgoing => {
+                (left_is(EDGE_SOURCE_COLUMN), right_is(EDGE_TARGET_COLUMN))
+            }
+            RelationshipDirection::Incoming => {
+                (left_is(EDGE_TARGET_COLUMN), right_is(EDGE_SOURCE_COLUMN))
+            }
+            RelationshipDirection::Undirected => {
+                match (left_is(EDGE_SOURCE_COLUMN), left_is(EDGE_TARGET_COLUMN)) {
+                    (Some(from_source), Some(from_target)) => (
+                        Some(or(from_source.clone(), from_target.clone())),
+                        or(
+                            Expr::Nested(Box::new(and(from_source, right_is(EDGE_TARGET_COLUMN)))),
+                            Expr::Nested(Box::new(and(from_target, right_is(EDGE_SOURCE_COLUMN)))),
+                        ),
+                    ),
+                    _ => (
+                        None,
+                        or(right_is(EDGE_TARGET_COLUMN), right_is(EDGE_SOURCE_COLUMN)),
+                    ),
+                }
+            }
+        };
+
+        let edge_table = edge_table(context, relationship, edge.clone())?;
+        match edge_on {
+            Some(edge_on) => tables.join(edge_table, edge_on, predicates),
+            None => tables.relation = Some(edge_table),
+        }
+        if context.is_bound(node) {
+            predicates.push(node_on);
+        } else if !is_anonymous(node) {
+            tables.join(
+                aliased_table(
+                    context.options,
+                    node_table(context, node)?,
+                    Some(right.clone()),
+                ),
+                node_on,
+                predicates,
+            );
+            context.bind(node);
+        }
+
+        left_id = Some(if context.is_bound(node) || !is_anonymous(node) {
+            column(&right, NODE_ID_COLUMN)
+        } else {
+            match relationship.direction {
+                RelationshipDirection::Outgoing => column(&edge, EDGE_TARGET_COLUMN),
+                RelationshipDirection::Incoming => column(&edge, EDGE_SOURCE_COLUMN),
+                RelationshipDirection::Undirected if steps.peek().is_some() => {
+                    return Err(CypherConversionError::UnsupportedPattern(format!(
+                        "anonymous node {node} between relationships must be reached by a \
+                         directed relationship"
+                    )))
+                }
+                RelationshipDirection::Undirected => column(&edge, EDGE_TARGET_COLUMN),
+            }
+        });
+        left = right;
+    }
+
+    if let Some(variable) = &path.variable {
+        let (Some(start_id), Some(end_id)) = (start_id, left_id) else {
+            return Err(CypherConversionError::UnsupportedPattern(format!(
+                "path {variable} must not start with an anonymous node reached by an \
+                 undirected relationship"
+            )));
+        };
+        context
+            .path_endpoints
+            .insert(variable.clone(), (start_id, end_id));
+    }
+
+    Ok(())
+}
+
+/// Whether a node is `()`: neither named, labeled nor constrained, so that
+/// any row of an edge identifies it without joining a node table.
+fn is_anonymous(node: &NodePattern) -> bool {
+    node.variable.is_none()
+        && node.labels.is_empty()
+        && node.properties.is_empty()
+        && node.parameter.is_none()
+}
+
+/// Adds a predicate on the row aliased `alias`, or on the only row if
+/// `None`, for each entry of an inline property map: an equality, an `IN`
+/// for a list value such as `{status: ['a', 'b']}`, or with
+/// [`ConversionOptions::range_maps`] a range, an inequality for an
+/// entry such as `{status <> 'deleted'}`, or with
+/// [`ConversionOptions::operator_maps`] a comparison for each entry of an
+/// operator map such as `{age: {$gt: 18}}`.
+fn push_property_predicates(
+    options: &ConversionOptions,
+    alias: Option<&str>,
+    properties: &[(Ident, Expr)],
+    predicates: &mut Vec<Expr>,
+) -> Result<(), CypherConversionError> {
+    for (key, value) in properties {
+        match value {
+            Expr::Array(array) if options.range_maps => match array.elem.as_slice() {
+                [low, high] => predicates.push(binary_op(
+                    binary_op(
+                        property(options, alias, key),
+                        BinaryOperator::GtEq,
+                        low.clone(),
+                    ),
+                    BinaryOperator::And,
+                    binary_op(
+                        property(options, alias, key),
+                        BinaryOperator::LtEq,
+                        high.clone(),
+                    ),
+                )),
+                _ => {
+                    return Err(CypherConversionError::UnsupportedPattern(format!(
+                        "{key}: {value} is not a range of two values"
+                    )))
+                }
+            },
+            Expr::Array(array) => predicates.push(Expr::InList {
+                expr: Box::new(property(options, alias, key)),
+                list: array.elem.clone(),
+                negated: false,
+            }),
+            Expr::BinaryOp { op, right, .. } => predicates.push(binary_op(
+                property(options, alias, key),
+                op.clone(),
+                (**right).clone(),
+            )),
+            Expr::Dictionary(fields) if options.operator_maps => {
+                for field in fields {
+                    let op = match field.key.value.as_str() {
+                        "$gt" => BinaryOperator::Gt,
+                        "$gte" => BinaryOperator::GtEq,
+                        "$lt" => BinaryOperator::Lt,
+                        "$lte" => BinaryOperator::LtEq,
+                        "$ne" => BinaryOperator::NotEq,
+                        other => {
+                            return Err(CypherConversionError::UnsupportedPattern(format!(
+                                "unknown property operator '{other}'"
+                            )))
+                        }
+                    };
+                    predicates.push(binary_op(
+                        property(options, alias, key),
+                        op,
+                        (*field.value).clone(),
+                    ));
+                }
+            }
+            Expr::Function(_) => predicates.push(eq(
+                property(options, alias, key),
+                convert_expr(&mut ConversionContext::new(options), value)?,
+            )),
+            value => predicates.push(eq(property(options, alias, key), value.clone())),
+        }
+    }
+    Ok(())
+}
+
+/// Returns the column of a property, qualified with `alias` if any.
+fn property(options: &ConversionOptions, alias: Option<&str>, key: &Ident) -> Expr {
+    let key = options.target_dialect.column_ident(&key.value);
+    match alias {
+        Some(alias) => Expr::CompoundIdentifier(vec![Ident::new(alias), key]),
+        None => Expr::Identifier(key),
+    }
+}
+
+/// Returns the table backing a node: the CTE its variable is bound to, or
+/// else the table of its first label or the default label.
+fn node_table(
+    context: &ConversionContext,
+    node: &NodePattern,
+) -> Result<String, CypherConversionError> {
+    if let Some(variable) = &node.variable {
+        if context.cte_variables.contains(variable) {
+            return Ok(variable.clone());
+        }
+    }
+    node_label(context.options, node).map(|label| label_table(context.options, label))
+}
+
+/// Returns the table backing a label: the one it maps to in
+/// [`ConversionOptions::label_tables`], or else the table named after it.
+fn label_table(options: &ConversionOptions, label: String) -> String {
+    options.label_tables.get(&label).cloned().unwrap_or(label)
+}
+
+/// Returns the first label of a node, or else the configured default.
+fn node_label(
+    options: &ConversionOptions,
+    node: &NodePattern,
+) -> Result<String, CypherConversionError> {
+    node.labels
+        .first()
+        .or(options.default_label.as_ref())
+        .cloned()
+        .ok_or_else(|| CypherConversionError::UnsupportedPattern("node label required".to_string()))
+}
+
+/// Returns the table backing a relationship, aliased `alias`: the table of
+/// its type, or the default edge table when it has none.
+///
+/// A relationship with alternative types, such as `[:FOLLOWS|BLOCKS]`,
+/// matches a row of any of their tables, so it is backed by the
+/// `UNION ALL` of those tables. They must therefore share their columns.
+fn edge_table(
+    context: &ConversionContext,
+    relationship: &RelationshipPattern,
+    alias: String,
+) -> Result<TableFactor, CypherConversionError> {
+    match relationship.types.as_slice() {
+        [] => match &context.options.default_edge_table {
+            Some(table) => Ok(aliased_table(context.options, table.clone(), Some(alias))),
+            None => Err(CypherConversionError::UnsupportedPattern(
+                "relationship type required".to_string(),
+            )),
+        },
+        [relationship_type] => Ok(aliased_table(
+            context.options,
+            relationship_type.clone(),
+            Some(alias),
+        )),
+        [first, rest @ ..] => {
+            let select_all = |relationship_type: &String| {
+                SetExpr::Select(Box::new(create_select(
+                    vec![SelectItem::Wildcard(WildcardAdditionalOptions::default())],
+                    vec![TableWithJoins {
+                        relation: table_factor(relationship_type.clone(), None),
+                        joins: vec![],
+                    }],
+                    None,
+                )))
+            };
+            let union = rest
+                .iter()
+                .fold(select_all(first), |left, right| SetExpr::SetOperation {
+                    op: SetOperator::Union,
+                    set_quantifier: SetQuantifier::All,
+                    left: Box::new(left),
+                    right: Box::new(select_all(right)),
+                });
+            Ok(TableFactor::Derived {
+                lateral: false,
+                subquery: Box::new(create_query(union)),
+                alias: Some(TableAlias {
+                    name: Ident::new(alias),
+                    columns: vec![],
+                }),
+            })
+        }
+    }
+}
+
+/// Returns the name of a table, qualified with its schema as in
+/// `analytics.persons`.
+fn object_name(table_name: &str) -> ObjectName {
+    ObjectName(
+        table_name
+            .split('.')
+            .map(|part| ObjectNamePart::Identifier(Ident::new(part)))
+            .collect(),
+    )
+}
+
+fn table_factor(table_name: String, alias: Option<String>) -> TableFactor {
+    TableFactor::Table {
+        name: object_name(&table_name),
+        alias: alias.map(|var| TableAlias {
+            name: Ident::new(var),
+            columns: vec![],
+        }),
+        args: None,
+        with_hints: vec![],
+        version: None,
+        with_ordinality: false,
+        partitions: vec![],
+        json_path: None,
+        index_hints: vec![],
+        sample: None,
+    }
+}
+
+/// Returns a table factor like [`table_factor`], with the alias column
+/// list of [`ConversionOptions::table_alias_columns`] for the table, if any.
+fn aliased_table(
+    options: &ConversionOptions,
+    table_name: String,
+    alias: Option<String>,
+) -> TableFactor {
+    let columns = options.table_alias_columns.get(&table_name).cloned();
+    let mut table = table_factor(table_name, alias);
+    if let (
+        TableFactor::Table {
+            alias: Some(alias), ..
+        },
+        Some(columns),
+    ) = (&mut table, columns)
+    {
+        alias.columns = columns
+            .into_iter()
+            .map(TableAliasColumnDef::from_name)
+            .collect();
+    }
+    table
+}
+
+fn inner_join(relation: TableFactor, on: Expr) -> Join {
+    Join {
+        relation,
+        global: false,
+        join_operator: JoinOperator::Join(JoinConstraint::On(on)),
+    }
+}
+
+/// Quotes the table, alias and column identifiers of a converted statement
+/// with double quotes, for [`ConversionOptions::ansi_quoting`].
+fn quote_identifiers(statement: &mut Statement) {
+    match statement {
+        Statement::Query(query) => quote_query(query),
+        Statement::Insert(insert) => {
+            if let TableObject::TableName(name) = &mut insert.table {
+                quote_object_name(name);
+            }
+            insert.columns.iter_mut().for_each(quote_ident);
+            if let Some(source) = &mut insert.source {
+                quote_query(source);
+            }
+            if let Some(OnInsert::OnConflict(OnConflict {
+                conflict_target: Some(ConflictTarget::Columns(columns)),
+                ..
+            })) = &mut insert.on
+            {
+                columns.iter_mut().for_each(quote_ident);
+            }
+            if let Some(returning) = &mut insert.returning {
+                returning.iter_mut().for_each(quote_select_item);
+            }
+        }
+        Statement::Update(update) => {
+            quote_table_with_joins(&mut update.table);
+            for assignment in &mut update.assignments {
+                if let AssignmentTarget::ColumnName(name) = &mut assignment.target {
+                    quote_object_name(name);
+                }
+                quote_expr(&mut assignment.value);
+            }
+            if let Some(selection) = &mut update.selection {
+                quote_expr(selection);
+            }
+        }
+        Statement::CreateTable(CreateTable {
+            query: Some(query), ..
+        }) => quote_query(query),
+        Statement::Delete(delete) => {
+            if let FromTable::WithFromKeyword(tables) = &mut delete.from {
+                tables.iter_mut().for_each(quote_table_with_joins);
+            }
+            if let Some(selection) = &mut delete.selection {
+                quote_expr(selection);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn quote_query(query: &mut Query) {
+    if let Some(with) = &mut query.with {
+        for cte in &mut with.cte_tables {
+            quote_ident(&mut cte.alias.name);
+            quote_query(&mut cte.query);
+        }
+    }
+    quote_set_expr(&mut query.body);
+    if let Some(OrderBy {
+        kind: OrderByKind::Expressions(exprs),
+        ..
+    }) = &mut query.order_by
+    {
+        exprs
+            .iter_mut()
+            .for_each(|order_by| quote_expr(&mut order_by.expr));
+    }
+    for operator in &mut query.pipe_operators {
+        match operator {
+            PipeOperator::Where { expr } => quote_expr(expr),
+            PipeOperator::OrderBy { exprs } => exprs
+                .iter_mut()
+                .for_each(|order_by| quote_expr(&mut order_by.expr)),
+            PipeOperator::Select { exprs } => exprs.iter_mut().for_each(quote_select_item),
+            _ => {}
+        }
+    }
+}
+
+fn quote_set_expr(body: &mut SetExpr) {
+    match body {
+        SetExpr::Select(select) => {
+            if let Some(Distinct::On(exprs)) = &mut select.distinct {
+                exprs.iter_mut().for_each(quote_expr);
+            }
+            select.projection.iter_mut().for_each(quote_select_item);
+            select.from.iter_mut().for_each(quote_table_with_joins);
+            if let Some(selection) = &mut select.selection {
+                quote_expr(selection);
+            }
+            if let GroupByExpr::Expressions(exprs, _) = &mut select.group_by {
+                exprs.iter_mut().for_each(quote_expr);
+            }
+        }
+        SetExpr::Query(query) => quote_query(query),
+        SetExpr::SetOperation { left, right, .. } => {
+            quote_set_expr(left);
+            quote_set_expr(right);
+        }
+        SetExpr::Values(values) => values.rows.iter_mut().flatten().for_each(quote_expr),
+        SetExpr::Insert(statement) => quote_identifiers(statement),
+        _ => {}
+    }
+}
+
+fn quote_select_item(item: &mut SelectItem) {
+    match item {
+        SelectItem::UnnamedExpr(expr) => quote_expr(expr),
+        SelectItem::ExprWithAlias { expr, alias } => {
+            quote_expr(expr);
+            quote_ident(alias);
+        }
+        SelectItem::QualifiedWildcard(SelectItemQualifiedWildcardKind::ObjectName(name), _) => {
+            quote_object_name(name)
+        }
+        _ => {}
+    }
+}
+
+fn quote_table_with_joins(table: &mut TableWithJoins) {
+    quote_table_factor(&mut table.relation);
+    for join in &mut table.joins {
+        quote_table_factor(&mut join.relation);
+        if let JoinOperator::Join(JoinConstraint::On(on)) = &mut join.join_operator {
+            quote_expr(on);
+        }
+    }
+}
+
+fn quote_table_factor(table: &mut TableFactor) {
+    let alias = match table {
+        TableFactor::Table { name, alias, .. } => {
+            quote_object_name(name);
+            alias
+        }
+        TableFactor::Derived {
+            subquery, alias, ..
+        } => {
+            quote_query(subquery);
+            alias
+        }
+        _ => return,
+    };
+    if let Some(alias) = alias {
+        quote_ident(&mut alias.name);
+        for column in &mut alias.columns {
+            quote_ident(&mut column.name);
+        }
+    }
+}
+
+fn quote_expr(expr: &mut Expr) {
+    match expr {
+        Expr::Identifier(ident) => quote_ident(ident),
+        Expr::CompoundIdentifier(idents) => idents.iter_mut().for_each(quote_ident),
+        Expr::BinaryOp { left, right, .. }
+        | Expr::AnyOp { left, right, .. }
+        | Expr::AllOp { left, right, .. }
+        | Expr::IsDistinctFrom(left, right)
+        | Expr::IsNotDistinctFrom(left, right) => {
+            quote_expr(left);
+            quote_expr(right);
+        }
+        Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } => {
+            quote_expr(expr);
+            quote_expr(pattern);
+        }
+        Expr::UnaryOp { expr, .. }
+        | Expr::Nested(expr)
+        | Expr::Cast { expr, .. }
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::IsTrue(expr)
+        | Expr::IsFalse(expr) => quote_expr(expr),
+        Expr::InList { expr, list, .. } => {
+            quote_expr(expr);
+            list.iter_mut().for_each(quote_expr);
+        }
+        Expr::InSubquery { expr, subquery, .. } => {
+            quote_expr(expr);
+            quote_query(subquery);
+        }
+        Expr::Exists { subquery, .. } | Expr::Subquery(subquery) => quote_query(subquery),
+        Expr::Between {
+            expr, low, high, ..
+        } => {
+            quote_expr(expr);
+            quote_expr(low);
+            quote_expr(high);
+        }
+        Expr::Substring {
+            expr,
+            substring_from,
+            substring_for,
+            ..
+        } => {
+            quote_expr(expr);
+            for operand in [substring_from, substring_for].into_iter().flatten() {
+                quote_expr(operand);
+            }
+        }
+        Expr::Case {
+            operand,
+            conditions,
+            else_result,
+            ..
+        } => {
+            for operand in [operand, else_result].into_iter().flatten() {
+                quote_expr(operand);
+            }
+            for when in conditions {
+                quote_expr(&mut when.condition);
+                quote_expr(&mut when.result);
+            }
+        }
+        Expr::Function(function) => {
+            if let FunctionArguments::List(list) = &mut function.args {
+                for arg in &mut list.args {
+                    if let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg {
+                        quote_expr(expr);
+                    }
+                }
+            }
+            if let Some(WindowType::WindowSpec(window)) = &mut function.over {
+                window.partition_by.iter_mut().for_each(quote_expr);
+                window
+                    .order_by
+                    .iter_mut()
+                    .for_each(|order_by| quote_expr(&mut order_by.expr));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn quote_object_name(name: &mut ObjectName) {
+    for part in &mut name.0 {
+        if let ObjectNamePart::Identifier(ident) = part {
+            quote_ident(ident);
+        }
+    }
+}
+
+fn quote_ident(ident: &mut Ident) {
+    ident.quote_style = Some('"');
+}
+
+fn column(alias: &str, column: &str) -> Expr {
+    Expr::CompoundIdentifier(vec![Ident::new(alias), Ident::new(column)])
+}
+
+fn function(name: &str, args: Vec<Expr>) -> Expr {
+    Expr::Function(Function {
+        name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new(name))]),
+        uses_odbc_syntax: false,
+        parameters: FunctionArguments::None,
+        args: FunctionArguments::List(FunctionArgumentList {
+            duplicate_treatment: None,
+            args: args
+                .into_iter()
+                .map(|arg| FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)))
+                .collect(),
+            clauses: vec![],
+        }),
+        filter: None,
+        null_treatment: None,
+        over: None,
+        within_group: vec![],
+    })
+}
+
+/// `COUNT(*)`
+fn count_rows() -> Expr {
+    let mut count = function("COUNT", vec![]);
+    if let Expr::Function(Function {
+        args: FunctionArguments::List(list),
+        ..
+    }) = &mut count
+    {
+        list.args
+            .push(FunctionArg::Unnamed(FunctionArgExpr::Wildcard));
+    }
+    count
+}
+
+fn binary_op(left: Expr, op: BinaryOperator, right: Expr) -> Expr {
+    Expr::BinaryOp {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    }
+}
+
+/// Splits a chain of `AND` and `XOR` into its operands and operators, in
+/// order. Parenthesized operands are not split.
+fn and_xor_chain<'a>(
+    expr: &'a Expr,
+    operands: &mut Vec<&'a Expr>,
+    operators: &mut Vec<BinaryOperator>,
+) {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: op @ (BinaryOperator::And | BinaryOperator::Xor),
+            right,
+        } => {
+            and_xor_chain(left, operands, operators);
+            operators.push(op.clone());
+            and_xor_chain(right, operands, operators);
+        }
+        operand => operands.push(operand),
+    }
+}
+
+fn mixes_and_xor(expr: &Expr) -> bool {
+    let mut operators = vec![];
+    and_xor_chain(expr, &mut vec![], &mut operators);
+    operators.contains(&BinaryOperator::And) && operators.contains(&BinaryOperator::Xor)
+}
+
+/// Regroups a chain mixing `AND` and `XOR` as Cypher reads it, with `AND`
+/// binding more tightly than `XOR`, where SQL parsers bind `XOR` more
+/// tightly: `a XOR b AND c` becomes `a XOR (b AND c)`. The parentheses keep
+/// that grouping in every database.
+fn cypher_xor_precedence(expr: &Expr) -> Expr {
+    let (mut operands, mut operators) = (vec![], vec![]);
+    and_xor_chain(expr, &mut operands, &mut operators);
+
+    let mut operands = operands.into_iter().cloned();
+    let mut terms = vec![];
+    let mut term = operands.next().expect("a chain has an operand");
+    for (operator, operand) in operators.into_iter().zip(operands) {
+        if operator == BinaryOperator::And {
+            term = and(term, operand);
+        } else {
+            terms.push(core::mem::replace(&mut term, operand));
+        }
+    }
+    terms.push(term);
+    terms
+        .into_iter()
+        .map(|term| match term {
+            term @ Expr::BinaryOp { .. } => Expr::Nested(Box::new(term)),
+            term => term,
+        })
+        .reduce(|left, right| binary_op(left, BinaryOperator::Xor, right))
+        .expect("a chain has a term")
+}
+
+/// Returns a comparison, splitting a Cypher chained comparison such as
+/// `18 <= n.age < 65` into one comparison per operator,
+/// `(18 <= n.age AND n.age < 65)`. Inclusive bounds on both sides become a
+/// `BETWEEN`.
+fn comparison(left: Expr, op: BinaryOperator, right: Expr) -> Expr {
+    match left {
+        Expr::BinaryOp {
+            left: low,
+            op:
+                first @ (BinaryOperator::Lt
+                | BinaryOperator::LtEq
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq),
+            right: middle,
+        } => match range(
+            binary_op(*low, first, (*middle).clone()),
+            binary_op(*middle, op, right),
+        ) {
+            between @ Expr::Between { .. } => between,
+            chain => Expr::Nested(Box::new(chain)),
+        },
+        left => binary_op(left, op, right),
+    }
+}
+
+/// Returns the conjunction of two predicates, or a `BETWEEN` when they
+/// bound the same value inclusively from either side, as in
+/// `n.age >= 18 AND n.age <= 65`.
+fn range(left: Expr, right: Expr) -> Expr {
+    // Splits `a <= b` or `b >= a` into `(a, b)`.
+    fn at_most(expr: &Expr) -> Option<(&Expr, &Expr)> {
+        match expr {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::LtEq,
+                right,
+            } => Some((left, right)),
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::GtEq,
+                right,
+            } => Some((right, left)),
+            _ => None,
+        }
+    }
+
+    let between = |expr: &Expr, low: &Expr, high: &Expr| Expr::Between {
+        expr: Box::new(expr.clone()),
+        negated: false,
+        low: Box::new(low.clone()),
+        high: Box::new(high.clone()),
+    };
+    match (at_most(&left), at_most(&right)) {
+        (Some((low, value)), Some((other, high))) if value == other => between(value, low, high),
+        (Some((value, high)), Some((low, other))) if value == other => between(value, low, high),
+        _ => and(left, right),
+    }
+}
+
+fn eq(left: Expr, right: Expr) -> Expr {
+    binary_op(left, BinaryOperator::Eq, right)
+}
+
+fn and(left: Expr, right: Expr) -> Expr {
+    binary_op(left, BinaryOperator::And, right)
+}
+
+fn or(left: Expr, right: Expr) -> Expr {
+    binary_op(left, BinaryOperator::Or, right)
+}
+
+/// Combines predicates with `AND`, parenthesizing any `OR` so that it keeps
+/// its meaning.
+fn conjunction(predicates: Vec<Expr>) -> Option<Expr> {
+    let nest_or = |expr: Expr| match expr {
+        Expr::BinaryOp {
+            op: BinaryOperator::Or,
+            ..
+        } => Expr::Nested(Box::new(expr)),
+        _ => expr,
+    };
+    if predicates.len() == 1 {
+        return predicates.into_iter().next();
+    }
+    predicates.into_iter().map(nest_or).reduce(and)
+}
+
+/// Combines the join predicates of a pattern, its property predicates and
+/// its `WHERE` clause. Property predicates are joined to the `WHERE` clause
+/// by [`ConversionOptions::property_predicate_combinator`].
+fn selection(
+    options: &ConversionOptions,
+    mut predicates: Vec<Expr>,
+    property_predicates: Vec<Expr>,
+    where_clause: Option<Expr>,
+) -> Option<Expr> {
+    let where_clause = match where_clause {
+        Some(where_clause) if options.explicit_bool => Some(explicit_bool(where_clause)),
+        where_clause => where_clause,
+    };
+    match (conjunction(property_predicates), where_clause) {
+        (Some(properties), Some(where_clause))
+            if options.property_predicate_combinator == PredicateCombinator::Or =>
+        {
+            predicates.push(or(properties, where_clause))
+        }
+        (properties, where_clause) => predicates.extend(properties.into_iter().chain(where_clause)),
+    }
+    conjunction(predicates)
+}
+
+/// Compares each bare property of a predicate to `true`, through `AND`,
+/// `OR` and `NOT`: `n.active AND NOT n.banned` becomes
+/// `n.active = true AND NOT n.banned = true`.
+fn explicit_bool(predicate: Expr) -> Expr {
+    match predicate {
+        Expr::Identifier(_) | Expr::CompoundIdentifier(_) => Expr::BinaryOp {
+            left: Box::new(predicate),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Value(Value::Boolean(true).into())),
+        },
+        Expr::BinaryOp {
+            left,
+            op: op @ (BinaryOperator::And | BinaryOperator::Or),
+            right,
+        } => Expr::BinaryOp {
+            left: Box::new(explicit_bool(*left)),
+            op,
+            right: Box::new(explicit_bool(*right)),
+        },
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr,
+        } => Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr: Box::new(explicit_bool(*expr)),
+        },
+        Expr::Nested(expr) => Expr::Nested(Box::new(explicit_bool(*expr))),
+        predicate => predicate,
+    }
+}
+
+fn create_query(body: SetExpr) -> Query {
+    Query {
+        with: None,
+        body: Box::new(body),
+        order_by: None,
+        limit_clause: None,
+        fetch: None,
+        locks: vec![],
+        for_clause: None,
+        settings: None,
+        format_clause: None,
+        pipe_operators: vec![],
+    }
+}
+
+fn create_select(
+    projection: Vec<SelectItem>,
+    from: Vec<TableWithJoins>,
+    selection: Option<Expr>,
+) -> Select {
+    Select {
+        select_token: AttachedToken::empty(),
+        distinct: None,
+        top: None,
+        top_before_distinct: false,
+        projection,
+        exclude: None,
+        into: None,
+        from,
+        lateral_views: vec![],
+        prewhere: None,
+        selection,
+        group_by: GroupByExpr::Expressions(vec![], vec![]),
+        cluster_by: vec![],
+        distribute_by: vec![],
+        sort_by: vec![],
+        having: None,
+        named_window: vec![],
+        qualify: None,
+        window_before_qualify: false,
+        value_table_mode: None,
+        connect_by: None,
+        flavor: SelectFlavor::Standard,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert_match(cypher: &str) -> String {
+        convert_match_with_options(cypher, &ConversionOptions::default())
+            .unwrap()
+            .to_string()
+    }
+
+    fn convert_match_with_options(
+        cypher: &str,
+        options: &ConversionOptions,
+    ) -> Result<Statement, CypherConversionError> {
+        let statements = Parser::parse_sql(&GenericDialect {}, cypher).unwrap();
+        match &statements[0] {
+            statement @ Statement::CypherQuery { .. } => convert_statement(statement, options),
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_graph_pattern_relationship() {
+        let graph =
+            GraphPattern::parse("( a : Person ) < - [ r : KNOWS ] - ( b : Person )").unwrap();
+        let path = &graph.paths[0];
+        assert_eq!(path.start.labels, vec!["Person".to_string()]);
+        assert_eq!(path.steps.len(), 1);
+
+        let (relationship, node) = &path.steps[0];
+        assert_eq!(relationship.variable, Some("r".to_string()));
+        assert_eq!(relationship.types, vec!["KNOWS".to_string()]);
+        assert_eq!(relationship.direction, RelationshipDirection::Incoming);
+        assert_eq!(node.variable, Some("b".to_string()));
+    }
+
+    #[test]
+    fn test_graph_pattern_error_position() {
+        let error = GraphPattern::parse("(n:Person {name 'Alice'})").unwrap_err();
+        assert_eq!(
+            error,
+            CypherConversionError::InvalidPattern {
+                message: "expected ':', found ''Alice''".to_string(),
+                position: Some(16),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "invalid pattern at position 16: expected ':', found ''Alice''"
+        );
+
+        let pattern = "(a)-[:KNOWS]->\n(b";
+        assert!(matches!(
+            GraphPattern::parse(pattern),
+            Err(CypherConversionError::InvalidPattern {
+                position: Some(position),
+                ..
+            }) if position == pattern.len()
+        ));
+        assert!(matches!(
+            GraphPattern::parse("(a)<-[:KNOWS]->(b)"),
+            Err(CypherConversionError::InvalidPattern {
+                position: Some(3),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_graph_pattern_display_round_trip() {
+        let canonical = "(a:Person {name: 'Alice', age: 30})<-[r:KNOWS|LIKES]-(b:Person), (c:Company $props)-[]-(), (:City)-[:IN {since: 2020}]->(d)";
+        let graph = GraphPattern::parse(canonical).unwrap();
+        assert_eq!(graph.to_string(), canonical);
+        assert_eq!(GraphPattern::parse(&graph.to_string()).unwrap(), graph);
+
+        let captured = "( n : Person { name : 'Alice' } ) - [ : KNOWS ] -> ( m )";
+        assert_eq!(
+            GraphPattern::parse(captured).unwrap().to_string(),
+            "(n:Person {name: 'Alice'})-[:KNOWS]->(m)"
+        );
+    }
+
+    #[test]
+    fn test_cypher_relationship_join() {
+        assert_eq!(
+            convert_match("MATCH (a:Person)-[:KNOWS]->(b:Person) RETURN b.name"),
+            "SELECT b.name FROM Person AS a \
+             JOIN KNOWS AS _r1 ON _r1.source_id = a.id \
+             JOIN Person AS b ON b.id = _r1.target_id"
+        );
+    }
+
+    #[test]
+    fn test_cypher_relationship_endpoints() {
+        let sql = convert_match("MATCH (a:Person)-[r:KNOWS]->(b:Person) RETURN startNode(r).name");
+        assert!(sql.starts_with("SELECT a.name FROM Person AS a"), "{sql}");
+
+        let sql = convert_match(
+            "MATCH (a:Person)<-[r:KNOWS]-(b:Person) RETURN startNode(r).name, endNode(r).name AS friend",
+        );
+        assert!(
+            sql.starts_with("SELECT b.name, a.name AS friend FROM Person AS a"),
+            "{sql}"
+        );
+
+        let result = convert_match_with_options(
+            "MATCH (a:Person)-[r:KNOWS]->(b:Person) RETURN startNode(x).name",
+            &ConversionOptions::default(),
+        );
+        assert_eq!(
+            result,
+            Err(CypherConversionError::UnsupportedPattern(
+                "x is not a relationship variable".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_cypher_self_join_distinct_aliases() {
+        let cypher = "MATCH (a:Person)-[:MANAGES]->(b:Person) RETURN a.name, b.name";
+        let Statement::Query(query) =
+            convert_match_with_options(cypher, &Default::default()).unwrap()
+        else {
+            panic!("Expected a query");
+        };
+        let SetExpr::Select(select) = query.body.as_ref() else {
+            panic!("Expected a SELECT");
+        };
+
+        let table_aliases: Vec<String> = core::iter::once(&select.from[0].relation)
+            .chain(select.from[0].joins.iter().map(|join| &join.relation))
+            .filter_map(|relation| match relation {
+                TableFactor::Table { name, alias, .. } if name.to_string() == "Person" => {
+                    alias.as_ref().map(|alias| alias.name.value.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(table_aliases, vec!["a".to_string(), "b".to_string()]);
+
+        assert_eq!(
+            query.to_string(),
+            "SELECT a.name, b.name FROM Person AS a \
+             JOIN MANAGES AS _r1 ON _r1.source_id = a.id \
+             JOIN Person AS b ON b.id = _r1.target_id"
+        );
+    }
+
+    #[test]
+    fn test_cypher_repeated_variable_closes_cycle() {
+        assert_eq!(
+            convert_match("MATCH (n:Person)-[:KNOWS]->(n) RETURN n.name"),
+            "SELECT n.name FROM Person AS n \
+             JOIN KNOWS AS _r1 ON _r1.source_id = n.id \
+             WHERE n.id = _r1.target_id"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:Person)-[:KNOWS]->(m:Person)-[:KNOWS]->(n) RETURN m.name"),
+            "SELECT m.name FROM Person AS n \
+             JOIN KNOWS AS _r1 ON _r1.source_id = n.id \
+             JOIN Person AS m ON m.id = _r1.target_id \
+             JOIN KNOWS AS _r2 ON _r2.source_id = m.id \
+             WHERE n.id = _r2.target_id"
+        );
+    }
+
+    #[test]
+    fn test_cypher_anonymous_node_aggregate() {
+        assert_eq!(
+            convert_match("MATCH (:Person) RETURN count(*)"),
+            "SELECT count(*) FROM Person"
+        );
+        assert_eq!(
+            convert_match("MATCH (:Person)-[:KNOWS]->(:Person) RETURN count(*) AS pairs"),
+            "SELECT count(*) AS pairs FROM Person AS _n1 \
+             JOIN KNOWS AS _r2 ON _r2.source_id = _n1.id \
+             JOIN Person AS _n3 ON _n3.id = _r2.target_id"
+        );
+    }
+
+    #[test]
+    fn test_cypher_relationship_alternative_types() {
+        assert_eq!(
+            convert_match(
+                "MATCH (a:Person)-[r:FOLLOWS|BLOCKS {active: true}]->(b:Person) RETURN b.name"
+            ),
+            "SELECT b.name FROM Person AS a \
+             JOIN (SELECT * FROM FOLLOWS UNION ALL SELECT * FROM BLOCKS) AS r ON r.source_id = a.id \
+             JOIN Person AS b ON b.id = r.target_id \
+             WHERE r.active = true"
+        );
+    }
+
+    #[test]
+    fn test_cypher_anonymous_relationship_default_edge_table() {
+        let options = ConversionOptions {
+            default_edge_table: Some("edges".to_string()),
+            ..Default::default()
+        };
+        let sql = convert_match_with_options("MATCH (a:Person)-[]->(b:Person) RETURN b", &options)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            sql,
+            "SELECT * FROM Person AS a \
+             JOIN edges AS _r1 ON _r1.source_id = a.id \
+             JOIN Person AS b ON b.id = _r1.target_id"
+        );
+    }
+
+    #[test]
+    fn test_cypher_unlabeled_node_default_label() {
+        let options = ConversionOptions {
+            default_label: Some("nodes".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options("MATCH (n) RETURN n", &options)
+                .unwrap()
+                .to_string(),
+            "SELECT * FROM nodes AS n"
+        );
+        assert_eq!(
+            convert_match_with_options("MATCH (a:Person)-[:KNOWS]->(b) RETURN b.name", &options)
+                .unwrap()
+                .to_string(),
+            "SELECT b.name FROM Person AS a \
+             JOIN KNOWS AS _r1 ON _r1.source_id = a.id \
+             JOIN nodes AS b ON b.id = _r1.target_id"
+        );
+        assert_eq!(
+            cypher_create_to_sql_with_options("(n {name: 'Alice'})", &options)
+                .unwrap()
+                .to_string(),
+            "INSERT INTO nodes (name) VALUES ('Alice')"
+        );
+    }
+
+    #[test]
+    fn test_cypher_unlabeled_node_without_default() {
+        let label_required = Err(CypherConversionError::UnsupportedPattern(
+            "node label required".to_string(),
+        ));
+        assert_eq!(
+            convert_match_with_options("MATCH (n) RETURN n", &ConversionOptions::default()),
+            label_required
+        );
+        assert_eq!(cypher_create_to_sql("(n {name: 'Alice'})"), label_required);
+    }
+
+    #[test]
+    fn test_cypher_anonymous_relationship_without_default() {
+        let result = convert_match_with_options(
+            "MATCH (a:Person)-[]->(b:Person) RETURN b",
+            &ConversionOptions::default(),
+        );
+        assert_eq!(
+            result,
+            Err(CypherConversionError::UnsupportedPattern(
+                "relationship type required".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_cypher_create_with_match_uses_cte() {
+        let cypher =
+            "CREATE (n:Person {name: 'Alice'}) WITH n MATCH (n)-[:KNOWS]->(m:Person) RETURN m.name";
+        let statements = Parser::parse_sql(&GenericDialect {}, cypher).unwrap();
+        let sql = convert_statement(&statements[0], &ConversionOptions::default())
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            sql,
+            "WITH n AS (INSERT INTO Person (name) VALUES ('Alice') RETURNING *) \
+             SELECT m.name FROM n AS n \
+             JOIN KNOWS AS _r1 ON _r1.source_id = n.id \
+             JOIN Person AS m ON m.id = _r1.target_id"
+        );
+    }
+
+    #[test]
+    fn test_cypher_create_with_star() {
+        let cypher =
+            "CREATE (n:Person {name: 'Alice'}) WITH * MATCH (n)-[:KNOWS]->(m:Person) RETURN n.name, m.name";
+        let statements = parse_cypher(cypher).unwrap();
+        assert_eq!(
+            statements[0].to_string(),
+            "CREATE ( n : Person { name : 'Alice' } ) WITH * \
+             MATCH ( n ) - [ : KNOWS ] -> ( m : Person ) RETURN n.name, m.name"
+        );
+        assert_eq!(
+            convert_statement(&statements[0], &ConversionOptions::default())
+                .unwrap()
+                .to_string(),
+            "WITH n AS (INSERT INTO Person (name) VALUES ('Alice') RETURNING *) \
+             SELECT n.name, m.name FROM n AS n \
+             JOIN KNOWS AS _r1 ON _r1.source_id = n.id \
+             JOIN Person AS m ON m.id = _r1.target_id"
+        );
+    }
+
+    #[test]
+    fn test_cypher_create_with_unknown_variable() {
+        let cypher = "CREATE (n:Person {name: 'Alice'}) WITH x MATCH (x:Person) RETURN x";
+        let statements = Parser::parse_sql(&GenericDialect {}, cypher).unwrap();
+        assert!(matches!(
+            convert_statement(&statements[0], &ConversionOptions::default()),
+            Err(CypherConversionError::UnsupportedPattern(_))
+        ));
+    }
+
+    #[test]
+    fn test_cypher_create_parameter_map() {
+        let pattern = "( n : Person $props )";
+        assert_eq!(
+            cypher_create_to_sql(pattern),
+            Err(CypherConversionError::UnsupportedPattern(
+                "parameter map $props has no known columns".to_string()
+            ))
+        );
+
+        let options = ConversionOptions {
+            parameter_map_placeholder: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            cypher_create_to_sql_with_options(pattern, &options)
+                .unwrap()
+                .to_string(),
+            "INSERT INTO Person VALUES ($props)"
+        );
+    }
+
+    #[test]
+    fn test_cypher_create_column_types() {
+        let mut options = ConversionOptions::default();
+        options.column_types.insert(
+            ("Person".to_string(), "count".to_string()),
+            DataType::Int(None),
+        );
+        assert_eq!(
+            cypher_create_to_sql_with_options("(n:Person {zip: '02134', count: '5'})", &options)
+                .unwrap()
+                .to_string(),
+            "INSERT INTO Person (zip, count) VALUES ('02134', CAST('5' AS INT))"
+        );
+    }
+
+    #[test]
+    fn test_cypher_create_list_property() {
+        let pattern = "( n : Post { title : 'Hi' , tags : [ 'a' , 'b, c' ] , scores : [ ] } )";
+        assert_eq!(
+            cypher_create_to_sql(pattern).unwrap().to_string(),
+            "INSERT INTO Post (title, tags, scores) VALUES ('Hi', ['a', 'b, c'], [])"
+        );
+
+        let options = ConversionOptions {
+            target_dialect: TargetDialect::PostgreSql,
+            ..Default::default()
+        };
+        assert_eq!(
+            cypher_create_to_sql_with_options(pattern, &options)
+                .unwrap()
+                .to_string(),
+            "INSERT INTO Post (title, tags, scores) VALUES ('Hi', ARRAY['a', 'b, c'], ARRAY[])"
+        );
+    }
+
+    #[test]
+    fn test_cypher_create_reserved_word_columns() {
+        let pattern = "(n:T {order: 1, group: 2, name: 'x'})";
+        assert_eq!(
+            cypher_create_to_sql(pattern).unwrap().to_string(),
+            "INSERT INTO T (\"order\", \"group\", name) VALUES (1, 2, 'x')"
+        );
+
+        let options = ConversionOptions {
+            target_dialect: TargetDialect::MySql,
+            ..Default::default()
+        };
+        assert_eq!(
+            cypher_create_to_sql_with_options(pattern, &options)
+                .unwrap()
+                .to_string(),
+            "INSERT INTO T (`order`, `group`, name) VALUES (1, 2, 'x')"
+        );
+    }
+
+    #[test]
+    fn test_cypher_create_nested_map_as_json() {
+        let pattern = "( n : T { name : 'x' , meta : { a : 1 , b : { c : 2 , d : [ 'e\"' ] } } } )";
+        assert_eq!(
+            cypher_create_to_sql(pattern),
+            Err(CypherConversionError::UnsupportedPattern(
+                "map value of meta requires map_as_json".to_string()
+            ))
+        );
+
+        let options = ConversionOptions {
+            map_as_json: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            cypher_create_to_sql_with_options(pattern, &options)
+                .unwrap()
+                .to_string(),
+            r#"INSERT INTO T (name, meta) VALUES ('x', CAST('{"a":1,"b":{"c":2,"d":["e\""]}}' AS JSON))"#
+        );
+
+        let options = ConversionOptions {
+            map_as_json: true,
+            target_dialect: TargetDialect::PostgreSql,
+            ..Default::default()
+        };
+        assert_eq!(
+            cypher_create_to_sql_with_options("(n:T {meta: {a: 1}})", &options)
+                .unwrap()
+                .to_string(),
+            r#"INSERT INTO T (meta) VALUES ('{"a":1}'::JSON)"#
+        );
+    }
+
+    #[test]
+    fn test_cypher_create_insert_style() {
+        let pattern = "(n:Person {name: 'Alice', age: 30})";
+        assert_eq!(
+            cypher_create_to_sql(pattern).unwrap().to_string(),
+            "INSERT INTO Person (name, age) VALUES ('Alice', 30)"
+        );
+
+        let options = ConversionOptions {
+            insert_style: InsertStyle::Select,
+            ..Default::default()
+        };
+        assert_eq!(
+            cypher_create_to_sql_with_options(pattern, &options)
+                .unwrap()
+                .to_string(),
+            "INSERT INTO Person (name, age) SELECT 'Alice' AS name, 30 AS age"
+        );
+    }
+
+    #[test]
+    fn test_cypher_lowercase_keywords() {
+        assert_eq!(
+            convert_match("match (n:Person) where n.age > 25 return n.name as x"),
+            "SELECT n.name AS x FROM Person AS n WHERE n.age > 25"
+        );
+    }
+
+    #[test]
+    fn test_cypher_where_parameter_equality() {
+        let cypher = "MATCH (n:Person) WHERE n.x = $val AND NOT n.y = 1 RETURN n.name";
+        assert_eq!(
+            convert_match(cypher),
+            "SELECT n.name FROM Person AS n WHERE n.x = $val AND NOT n.y = 1"
+        );
+
+        let options = ConversionOptions {
+            null_safe_equality: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT n.name FROM Person AS n \
+             WHERE n.x IS NOT DISTINCT FROM $val AND NOT n.y IS NOT DISTINCT FROM 1"
+        );
+    }
+
+    #[test]
+    fn test_cypher_string_concat_plus() {
+        let cypher = "MATCH (n:Person) RETURN n.first + ' ' + n.last AS name";
+        assert_eq!(
+            convert_match(cypher),
+            "SELECT n.first + ' ' + n.last AS name FROM Person AS n"
+        );
+
+        let options = ConversionOptions {
+            string_concat_plus: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT n.first || ' ' || n.last AS name FROM Person AS n"
+        );
+
+        let options = ConversionOptions {
+            string_concat_plus: true,
+            target_dialect: TargetDialect::MySql,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT CONCAT(CONCAT(n.first, ' '), n.last) AS name FROM Person AS n"
+        );
+    }
+
+    #[test]
+    fn test_cypher_property_predicates() {
+        let cypher = "MATCH (n:Person {name: 'Alice'})-[:KNOWS {since: 2020}]->(m:Person) \
+                      WHERE m.age > 30 OR m.age < 20 RETURN m.name";
+        assert_eq!(
+            convert_match(cypher),
+            "SELECT m.name FROM Person AS n \
+             JOIN KNOWS AS _r1 ON _r1.source_id = n.id \
+             JOIN Person AS m ON m.id = _r1.target_id \
+             WHERE n.name = 'Alice' AND _r1.since = 2020 AND (m.age > 30 OR m.age < 20)"
+        );
+        assert_eq!(
+            convert_match("MATCH (:Person {name: 'Alice'}) RETURN count(*)"),
+            "SELECT count(*) FROM Person AS _n1 WHERE _n1.name = 'Alice'"
+        );
+
+        let options = ConversionOptions {
+            property_predicate_combinator: PredicateCombinator::Or,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT m.name FROM Person AS n \
+             JOIN KNOWS AS _r1 ON _r1.source_id = n.id \
+             JOIN Person AS m ON m.id = _r1.target_id \
+             WHERE n.name = 'Alice' AND _r1.since = 2020 OR m.age > 30 OR m.age < 20"
+        );
+    }
+
+    #[test]
+    fn test_cypher_list_property_predicate() {
+        assert_eq!(
+            convert_match("MATCH (n:Task {status: ['open', 'blocked'], owner: 'Bob'}) RETURN n.id"),
+            "SELECT n.id FROM Task AS n WHERE n.status IN ('open', 'blocked') AND n.owner = 'Bob'"
+        );
+    }
+
+    #[test]
+    fn test_cypher_operator_map_property_predicate() {
+        let options = ConversionOptions {
+            operator_maps: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(
+                "MATCH (n:Person {age: {$gt: 18, $lte: 65}}) RETURN n.name",
+                &options
+            )
+            .unwrap()
+            .to_string(),
+            "SELECT n.name FROM Person AS n WHERE n.age > 18 AND n.age <= 65"
+        );
+        assert_eq!(
+            convert_match_with_options(
+                "MATCH (n:Person {status: {$ne: 'gone'}}) RETURN n",
+                &options
+            )
+            .unwrap()
+            .to_string(),
+            "SELECT * FROM Person AS n WHERE n.status <> 'gone'"
+        );
+        assert_eq!(
+            convert_match_with_options("MATCH (n:Person {age: {$eq: 1}}) RETURN n", &options),
+            Err(CypherConversionError::UnsupportedPattern(
+                "unknown property operator '$eq'".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_convert_where() {
+        let predicate = "n.age > 18 AND n.active";
+        assert_eq!(
+            convert_where(predicate, Some("p")).unwrap().to_string(),
+            "p.age > 18 AND p.active"
+        );
+        assert_eq!(
+            convert_where(predicate, None).unwrap().to_string(),
+            "age > 18 AND active"
+        );
+        assert_eq!(
+            convert_where("n.name STARTS WITH 'A'", Some("p"))
+                .unwrap()
+                .to_string(),
+            "p.name LIKE 'A%'"
+        );
+        let predicate = "point.distance(n.loc, $origin) < 100";
+        assert_eq!(
+            convert_where(predicate, Some("p")).unwrap().to_string(),
+            "point.distance(p.loc, $origin) < 100"
+        );
+        let options = ConversionOptions {
+            spatial: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_where_with_options(predicate, None, &options)
+                .unwrap()
+                .to_string(),
+            "ST_Distance(loc, $origin) < 100"
+        );
+        assert!(matches!(
+            convert_where("n.age > 18 RETURN n", None),
+            Err(CypherConversionError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_qualified_wildcard() {
+        let options = ConversionOptions {
+            qualified_wildcard: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options("MATCH (n:Person)-[:KNOWS]->(m:Person) RETURN n", &options)
+                .unwrap()
+                .to_string(),
+            "SELECT n.* FROM Person AS n \
+             JOIN KNOWS AS _r1 ON _r1.source_id = n.id \
+             JOIN Person AS m ON m.id = _r1.target_id"
+        );
+    }
+
+    #[test]
+    fn test_underscore_separated_property_number() {
+        assert_eq!(
+            convert_match("MATCH (n:T {big: 1_000_000, neg: -1_5}) RETURN n"),
+            "SELECT * FROM T AS n WHERE n.big = 1000000 AND n.neg = -15"
+        );
+    }
+
+    // `BigDecimal` normalizes the exponent notation
+    #[cfg(not(feature = "bigdecimal"))]
+    #[test]
+    fn test_scientific_property_number() {
+        assert_eq!(
+            convert_match("MATCH (n:T {sci: 1.5E3, small: 2.5e-10}) RETURN n"),
+            "SELECT * FROM T AS n WHERE n.sci = 1.5E3 AND n.small = 2.5e-10"
+        );
+    }
+
+    #[test]
+    fn test_order_by_nulls_ordering() {
+        let cypher = "MATCH (n:Person) RETURN n.name ORDER BY n.age DESC, n.name NULLS FIRST";
+        let options = ConversionOptions {
+            nulls_ordering: Some(NullsOrdering::Last),
+            target_dialect: TargetDialect::PostgreSql,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT n.name FROM Person AS n ORDER BY n.age DESC NULLS LAST, n.name NULLS FIRST"
+        );
+
+        let options = ConversionOptions {
+            target_dialect: TargetDialect::MySql,
+            ..options
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT n.name FROM Person AS n \
+             ORDER BY n.age IS NULL ASC, n.age DESC, n.name IS NULL DESC, n.name"
+        );
+    }
+
+    #[test]
+    fn test_cross_variable_property_predicate() {
+        assert_eq!(
+            parse_simple_value("a.id").unwrap(),
+            Expr::CompoundIdentifier(vec![Ident::new("a"), Ident::new("id")])
+        );
+        assert_eq!(
+            convert_match("MATCH (a:Person), (b:Person {manager_id: a.id}) RETURN b.name"),
+            "SELECT b.name FROM Person AS a, Person AS b WHERE b.manager_id = a.id"
+        );
+    }
+
+    #[test]
+    fn test_relationship_type_with_alias() {
+        assert_eq!(
+            convert_match("MATCH (a:Person)-[r:KNOWS]->(b:Person) RETURN b.name, type(r) AS rel"),
+            "SELECT b.name, 'KNOWS' AS rel FROM Person AS a \
+             JOIN KNOWS AS r ON r.source_id = a.id \
+             JOIN Person AS b ON b.id = r.target_id"
+        );
+        assert_eq!(
+            convert_match_with_options(
+                "MATCH (a:Person)-[r:FOLLOWS|BLOCKS]->(b:Person) RETURN type(r) AS rel",
+                &ConversionOptions::default()
+            ),
+            Err(CypherConversionError::UnsupportedPattern(
+                "type(r) of a relationship without a single type".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_string_predicates_to_like() {
+        assert_eq!(
+            convert_match(
+                "MATCH (n:Person) WHERE n.name STARTS WITH 'Al' AND n.email ENDS WITH '.org' \
+                 AND n.bio CONTAINS $word RETURN n.name"
+            ),
+            "SELECT n.name FROM Person AS n \
+             WHERE n.name LIKE 'Al%' AND n.email LIKE '%.org' AND n.bio LIKE '%' || $word || '%'"
+        );
+    }
+
+    #[test]
+    fn test_like_wildcard_escaping_warning() {
+        let (sql, warnings) = try_convert_with_warnings(
+            "MATCH (n:Product) WHERE n.label CONTAINS '50%' RETURN n.label",
+            &ConversionOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            r"SELECT n.label FROM Product AS n WHERE n.label LIKE '%50\%%' ESCAPE '\'"
+        );
+        assert_eq!(
+            warnings,
+            vec![ConversionWarning::EscapedLikeWildcards {
+                operator: CypherStringOperator::Contains,
+                pattern: "50%".to_string(),
+            }]
+        );
+
+        let (_, warnings) = try_convert_with_warnings(
+            "MATCH (n:Product) WHERE n.label STARTS WITH '50' RETURN n.label",
+            &ConversionOptions::default(),
+        )
+        .unwrap();
+        assert!(warnings.is_empty());
+
+        let (_, warnings) = try_convert_with_warnings(
+            "MATCH (n:Product) WHERE EXISTS { MATCH (n)-[:IN]->(c:Category) \
+             WHERE c.name ENDS WITH '_sale' } RETURN n.label",
+            &ConversionOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            warnings,
+            vec![ConversionWarning::EscapedLikeWildcards {
+                operator: CypherStringOperator::EndsWith,
+                pattern: "_sale".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_trailing_comma_property_map() {
+        assert_eq!(
+            convert_match("MATCH (n:Person {name: 'A', tags: ['x', 'y',], age: 30,}) RETURN n"),
+            "SELECT * FROM Person AS n WHERE n.name = 'A' AND n.tags IN ('x', 'y') AND n.age = 30"
+        );
+        assert_eq!(
+            cypher_create_to_sql("(n:Person {name: 'A', age: 30,})")
+                .unwrap()
+                .to_string(),
+            "INSERT INTO Person (name, age) VALUES ('A', 30)"
+        );
+    }
+
+    #[test]
+    fn test_double_quoted_property_keys() {
+        assert_eq!(
+            convert_match(r#"MATCH (n:Person {"name": "Alice", "first name": 'Al'}) RETURN n"#),
+            r#"SELECT * FROM Person AS n WHERE n.name = 'Alice' AND n."first name" = 'Al'"#
+        );
+        assert_eq!(
+            cypher_create_to_sql(r#"(n:Person {"name": "Alice"})"#)
+                .unwrap()
+                .to_string(),
+            "INSERT INTO Person (name) VALUES ('Alice')"
+        );
+    }
+
+    #[test]
+    fn test_node_id_in_list() {
+        assert_eq!(
+            convert_match("MATCH (n:Person) WHERE id(n) IN [1, 2, 3] RETURN n.name"),
+            "SELECT n.name FROM Person AS n WHERE n.id IN (1, 2, 3)"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:Person) WHERE NOT id(n) IN [4] RETURN id(n) AS id"),
+            "SELECT n.id AS id FROM Person AS n WHERE NOT n.id IN (4)"
+        );
+    }
+
+    #[test]
+    fn test_cypher_exists_subquery() {
+        assert_eq!(
+            convert_match(
+                "MATCH (n:Person) WHERE EXISTS { MATCH (n)-[:KNOWS]->(m:Person) WHERE m.age > 30 } RETURN n.name"
+            ),
+            "SELECT n.name FROM Person AS n WHERE EXISTS (\
+             SELECT 1 FROM KNOWS AS _r1 JOIN Person AS m ON m.id = _r1.target_id \
+             WHERE _r1.source_id = n.id AND m.age > 30)"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:Person) WHERE NOT EXISTS { MATCH (n)-[:KNOWS]->() } RETURN n"),
+            "SELECT * FROM Person AS n WHERE NOT EXISTS (\
+             SELECT 1 FROM KNOWS AS _r1 WHERE _r1.source_id = n.id)"
+        );
+    }
+
+    #[test]
+    fn test_cypher_negated_pattern_predicate() {
+        assert_eq!(
+            convert_match(
+                "MATCH (n:Person), (m:Person) WHERE NOT (n)-[:BLOCKED]->(m) RETURN m.name"
+            ),
+            "SELECT m.name FROM Person AS n, Person AS m WHERE NOT EXISTS (\
+             SELECT 1 FROM BLOCKED AS _r1 WHERE _r1.source_id = n.id AND m.id = _r1.target_id)"
+        );
+    }
+
+    #[test]
+    fn test_cypher_in_match_subquery() {
+        assert_eq!(
+            convert_match(
+                "MATCH (n:Person) WHERE n.dept_id IN (MATCH (d:Dept) WHERE d.name = 'R&D' RETURN d.id) RETURN n.name"
+            ),
+            "SELECT n.name FROM Person AS n WHERE n.dept_id IN (\
+             SELECT d.id FROM Dept AS d WHERE d.name = 'R&D')"
+        );
+        assert_eq!(
+            convert_match(
+                "MATCH (n:Person) WHERE n.id NOT IN (MATCH (n)-[:KNOWS]->(m:Person) RETURN m.id) RETURN n"
+            ),
+            "SELECT * FROM Person AS n WHERE n.id NOT IN (\
+             SELECT m.id FROM KNOWS AS _r1 JOIN Person AS m ON m.id = _r1.target_id \
+             WHERE _r1.source_id = n.id)"
+        );
+    }
+
+    #[test]
+    fn test_cypher_union() {
+        assert_eq!(
+            try_convert(
+                "MATCH (n:Person) RETURN n.name AS name \
+                 UNION MATCH (c:Company) RETURN c.title AS name \
+                 UNION ALL RETURN 'nobody' AS name"
+            )
+            .unwrap(),
+            "SELECT n.name AS name FROM Person AS n \
+             UNION SELECT c.title AS name FROM Company AS c \
+             UNION ALL SELECT 'nobody' AS name"
+        );
+
+        assert_eq!(
+            try_convert("MATCH (n:Person) RETURN n.name UNION MATCH (c:Company) RETURN c.name"),
+            Err(CypherConversionError::UnionColumnMismatch {
+                left: vec!["n.name".to_string()],
+                right: vec!["c.name".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_cypher_order_by_mixed_directions() {
+        let cypher = "MATCH (n:Person) RETURN n.a ORDER BY n.a ASC, n.b DESC, n.c";
+        let Statement::Query(query) =
+            convert_match_with_options(cypher, &Default::default()).unwrap()
+        else {
+            panic!("Expected a query");
+        };
+        let Some(OrderByKind::Expressions(exprs)) = query.order_by.as_ref().map(|o| &o.kind) else {
+            panic!("Expected ORDER BY expressions");
+        };
+        // No keyword leaves the direction unset, which is ascending in SQL as in Cypher.
+        assert_eq!(
+            exprs.iter().map(|e| e.options.asc).collect::<Vec<_>>(),
+            vec![Some(true), Some(false), None]
+        );
+        assert_eq!(
+            query.to_string(),
+            "SELECT n.a FROM Person AS n ORDER BY n.a ASC, n.b DESC, n.c"
+        );
+    }
+
+    #[test]
+    fn test_cypher_return_distinct_applies_to_row() {
+        let cypher = "MATCH (a:Person)-[:KNOWS]->(b:Person) RETURN DISTINCT a.x, b.y";
+        let Statement::Query(query) =
+            convert_match_with_options(cypher, &Default::default()).unwrap()
+        else {
+            panic!("Expected a query");
+        };
+        let SetExpr::Select(select) = query.body.as_ref() else {
+            panic!("Expected a SELECT");
+        };
+        assert_eq!(select.distinct, Some(Distinct::Distinct));
+        assert_eq!(select.projection.len(), 2);
+        assert!(query
+            .to_string()
+            .starts_with("SELECT DISTINCT a.x, b.y FROM Person AS a"));
+    }
+
+    #[test]
+    fn test_cypher_order_by_ordinal() {
+        let cypher = "MATCH (n:Person) RETURN n.a, n.b ORDER BY 2 DESC";
+        let Statement::Query(query) =
+            convert_match_with_options(cypher, &Default::default()).unwrap()
+        else {
+            panic!("Expected a query");
+        };
+        let Some(OrderByKind::Expressions(exprs)) = query.order_by.as_ref().map(|o| &o.kind) else {
+            panic!("Expected ORDER BY expressions");
+        };
+        assert_eq!(exprs[0].expr, Expr::Value(number(2).into()));
+        assert_eq!(
+            query.to_string(),
+            "SELECT n.a, n.b FROM Person AS n ORDER BY 2 DESC"
+        );
+    }
+
+    #[test]
+    fn test_cypher_order_by_unprojected_column() {
+        assert_eq!(
+            convert_match("MATCH (n:Person) RETURN n.name ORDER BY n.created"),
+            "SELECT n.name FROM Person AS n ORDER BY n.created"
+        );
+    }
+
+    #[test]
+    fn test_cypher_where_comparison_operators() {
+        for op in ["=", "<", "<=", ">", ">=", "<>"] {
+            let cypher = format!("MATCH (n:Person) WHERE n.age {op} 30 RETURN n.name");
+            assert_eq!(
+                convert_match(&cypher),
+                format!("SELECT n.name FROM Person AS n WHERE n.age {op} 30")
+            );
+        }
+    }
+
+    #[test]
+    fn test_graph_pattern_start_variable() {
+        let variable = |pattern| {
+            GraphPattern::parse(pattern).unwrap().paths[0]
+                .start
+                .variable
+                .clone()
+        };
+        assert_eq!(variable("(n:Person)"), Some("n".to_string()));
+        assert_eq!(variable("(abc:Person)"), Some("abc".to_string()));
+        assert_eq!(variable("(:Person)"), None);
+    }
+
+    #[test]
+    fn test_cypher_to_sql_simple() {
+        let pattern = "(n:Person)";
+        let where_clause = None;
+        let return_items = vec![SelectItem::UnnamedExpr(Expr::CompoundIdentifier(vec![
+            Ident::new("n"),
+            Ident::new("name"),
+        ]))];
+
+        let result = cypher_to_sql(pattern, &where_clause, &return_items);
+        assert!(result.is_ok());
+
+        let sql_stmt = result.unwrap();
+        let sql_str = sql_stmt.to_string();
+
+        println!("Generated SQL: {}", sql_str);
+        assert!(sql_str.contains("SELECT"));
+        assert!(sql_str.contains("FROM"));
+        assert!(sql_str.contains("Person"));
+    }
+
+    #[test]
+    fn test_cypher_return_whole_node() {
+        let pattern = "(n:Person)";
+        let where_clause = None;
+        // RETURN n (just the variable, not a property)
+        let return_items = vec![SelectItem::UnnamedExpr(Expr::Identifier(Ident::new("n")))];
+
+        let result = cypher_to_sql(pattern, &where_clause, &return_items);
+        assert!(result.is_ok());
+
+        let sql_stmt = result.unwrap();
+        let sql_str = sql_stmt.to_string();
+
+        println!("Generated SQL for RETURN n: {}", sql_str);
+        // Should be: SELECT * FROM Person AS n
+        assert!(sql_str.contains("SELECT *") || sql_str.contains("SELECT*"));
+        assert!(sql_str.contains("FROM Person"));
+    }
+
+    #[test]
+    fn test_cypher_return_literals_without_match() {
+        assert_eq!(
+            try_convert("RETURN 'hello' AS greeting, 42 AS answer").unwrap(),
+            "SELECT 'hello' AS greeting, 42 AS answer"
+        );
+        assert_eq!(try_convert("RETURN 'hello'").unwrap(), "SELECT 'hello'");
+        assert_eq!(
+            try_convert("RETURN 1 + 1 AS two").unwrap(),
+            "SELECT 1 + 1 AS two"
+        );
+        assert_eq!(
+            try_convert("RETURN 1 AS x UNION RETURN 2 AS x").unwrap(),
+            "SELECT 1 AS x UNION SELECT 2 AS x"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:Person) RETURN n.name, 'person' AS kind"),
+            "SELECT n.name, 'person' AS kind FROM Person AS n"
+        );
+    }
+
+    #[test]
+    fn test_return_literals_from_dual_on_mysql() {
+        let cypher = "RETURN 'hello' AS greeting, 42 AS answer";
+        for (target_dialect, expected) in [
+            (
+                TargetDialect::MySql,
+                "SELECT 'hello' AS greeting, 42 AS answer FROM DUAL",
+            ),
+            (
+                TargetDialect::PostgreSql,
+                "SELECT 'hello' AS greeting, 42 AS answer",
+            ),
+        ] {
+            let options = ConversionOptions {
+                target_dialect,
+                ..Default::default()
+            };
+            assert_eq!(
+                try_convert_with_options(cypher, &options).unwrap(),
+                expected
+            );
+        }
+        let options = ConversionOptions {
+            target_dialect: TargetDialect::MySql,
+            ..Default::default()
+        };
+        assert_eq!(
+            try_convert_with_options("RETURN 1", &options).unwrap(),
+            "SELECT 1 FROM DUAL"
+        );
+    }
+
+    #[test]
+    fn test_cypher_return_whole_node_with_column() {
+        assert_eq!(
+            convert_match("MATCH (n:Person) RETURN n, n.age"),
+            "SELECT n.*, n.age FROM Person AS n"
+        );
+    }
+
+    #[test]
+    fn test_parse_cypher() {
+        let statements = parse_cypher("MATCH (n:Person) RETURN n.name").unwrap();
+        assert_eq!(statements.len(), 1);
+        assert!(matches!(statements[0], Statement::CypherQuery { .. }));
+        assert!(parse_cypher("MATCH (n:Person").is_err());
+    }
+
+    #[test]
+    fn test_try_convert() {
+        assert_eq!(
+            try_convert("MATCH (n:Person) RETURN n.name").unwrap(),
+            "SELECT n.name FROM Person AS n"
+        );
+        assert!(matches!(
+            try_convert("MATCH (n:Person"),
+            Err(CypherConversionError::ParseError(_))
+        ));
+        assert!(matches!(
+            try_convert(""),
+            Err(CypherConversionError::UnsupportedStatement(_))
+        ));
+    }
+
+    #[test]
+    fn test_convert_to_parameterized() {
+        assert_eq!(
+            convert_to_parameterized(
+                "MATCH (n:Person {name: 'Alice'})-[:KNOWS {since: 2020}]->(m:Person {id: $id}) \
+                 WHERE m.age > 30 RETURN m.name"
+            )
+            .unwrap(),
+            (
+                "SELECT m.name FROM Person AS n \
+                 JOIN KNOWS AS _r1 ON _r1.source_id = n.id \
+                 JOIN Person AS m ON m.id = _r1.target_id \
+                 WHERE n.name = ? AND _r1.since = ? AND m.id = $id AND m.age > 30"
+                    .to_string(),
+                vec![Value::SingleQuotedString("Alice".to_string()), number(2020),]
+            )
+        );
+        assert_eq!(
+            convert_to_parameterized("CREATE (n:Person {name: 'Alice', active: true})").unwrap(),
+            (
+                "INSERT INTO Person (name, active) VALUES (?, ?)".to_string(),
+                vec![
+                    Value::SingleQuotedString("Alice".to_string()),
+                    Value::Boolean(true),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_try_convert_pretty() {
+        let options = ConversionOptions {
+            pretty: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            try_convert_with_options(
+                "MATCH (n:Person) WHERE n.age > 30 RETURN n.name, n.age",
+                &options
+            )
+            .unwrap(),
+            "SELECT\n  n.name,\n  n.age\nFROM\n  Person AS n\nWHERE\n  n.age > 30"
+        );
+    }
+
+    #[test]
+    fn test_try_convert_never_panics() {
+        const FRAGMENTS: &[&str] = &[
+            "MATCH", "CREATE", "RETURN", "WHERE", "EXISTS", "WITH", "AS", "NOT", "(", ")", "[",
+            "]", "{", "}", "-", "->", "<", ">", ":", ",", ".", "|", "*", "=", ";", "'", "\"",
+            "'a'", "n", "Person", "$p", "1", "-1", "é", "🙂", " ", " ",
+        ];
+
+        // A fixed linear congruential generator keeps the inputs reproducible.
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = |bound: usize| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) as usize % bound
+        };
+
+        for _ in 0..5000 {
+            let len = next(24);
+            let input: String = (0..len).map(|_| FRAGMENTS[next(FRAGMENTS.len())]).collect();
+            let _ = try_convert(&input);
+            let _ = try_convert(&format!("MATCH {input} RETURN n"));
+            let _ = try_convert(&format!("CREATE (n:Person {{{input}}})"));
+        }
+
+        for garbage in [
+            "'",
+            "\"",
+            "(((",
+            "}{",
+            "MATCH ( RETURN",
+            "CREATE ('",
+            "\u{0}",
+        ] {
+            assert!(try_convert(garbage).is_err(), "{garbage}");
+        }
+    }
+
+    #[test]
+    fn test_whole_node_return_keeps_alias_for_order_by() {
+        assert_eq!(
+            convert_match("MATCH (n:Person) WHERE n.age > 30 RETURN n ORDER BY n.created"),
+            "SELECT * FROM Person AS n WHERE n.age > 30 ORDER BY n.created"
+        );
+    }
+
+    #[test]
+    fn test_aggregate_implicit_group_by() {
+        assert_eq!(
+            convert_match("MATCH (n:Person) RETURN n.city, 'x' AS tag, count(*) + 1 AS c"),
+            "SELECT n.city, 'x' AS tag, count(*) + 1 AS c FROM Person AS n GROUP BY n.city"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:Person) RETURN 'all' AS g, count(*) AS c"),
+            "SELECT 'all' AS g, count(*) AS c FROM Person AS n"
+        );
+        assert_eq!(
+            convert_match(
+                "MATCH (n:Person) RETURN n.city, stDev(n.age) AS s, PERCENTILECONT(n.age, 0.5) AS p"
+            ),
+            "SELECT n.city, stDev(n.age) AS s, PERCENTILECONT(n.age, 0.5) AS p \
+             FROM Person AS n GROUP BY n.city"
+        );
+        assert_eq!(
+            convert_match_with_options(
+                "MATCH (n:Person) RETURN n, count(*)",
+                &ConversionOptions::default()
+            ),
+            Err(CypherConversionError::UnsupportedPattern(
+                "RETURN n alongside an aggregate".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_collect_to_array_agg() {
+        assert_eq!(
+            convert_match("MATCH (n:Person) RETURN collect(n.city) AS cities"),
+            "SELECT ARRAY_AGG(n.city) AS cities FROM Person AS n"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:Person) RETURN collect(DISTINCT toString(n.zip))"),
+            "SELECT ARRAY_AGG(DISTINCT CAST(n.zip AS VARCHAR)) FROM Person AS n"
+        );
+    }
+
+    #[test]
+    fn test_to_string_cast_type_per_dialect() {
+        let cypher = "MATCH (n:Person) RETURN toString(n.age)";
+        for (target_dialect, expected) in [
+            (TargetDialect::Generic, "VARCHAR"),
+            (TargetDialect::PostgreSql, "TEXT"),
+            (TargetDialect::MySql, "CHAR"),
+        ] {
+            let options = ConversionOptions {
+                target_dialect,
+                ..Default::default()
+            };
+            assert_eq!(
+                convert_match_with_options(cypher, &options)
+                    .unwrap()
+                    .to_string(),
+                format!("SELECT CAST(n.age AS {expected}) FROM Person AS n")
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_float_division() {
+        assert_eq!(
+            convert_match("MATCH (n:T) RETURN toFloat(n.a) / n.b AS ratio"),
+            "SELECT CAST(n.a AS DOUBLE) / n.b AS ratio FROM T AS n"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:T) RETURN n.b / toFloat(n.a + 1)"),
+            "SELECT n.b / CAST(n.a + 1 AS DOUBLE) FROM T AS n"
+        );
+    }
 
-    let sql_projection = convert_return_items(return_items);
+    #[test]
+    fn test_detach_delete_deletes_edges_before_node() {
+        let options = ConversionOptions {
+            edge_tables: vec!["WORKS_AT".to_string()],
+            ..Default::default()
+        };
+        let convert = |cypher: &str| {
+            let statement = parse_cypher(cypher).unwrap().remove(0);
+            convert_statements(&statement, &options)
+                .unwrap()
+                .iter()
+                .map(|statement| statement.to_string())
+                .collect::<Vec<_>>()
+        };
 
-    let select = create_select(
-        sql_projection,
-        vec![from_table],
-        where_clause.clone(),
-    );
+        // The node is selected from the ids read before its KNOWS rows, which
+        // the pattern joins, are deleted
+        assert_eq!(
+            convert("MATCH (n:Person)-[:KNOWS]->(m:Person) WHERE m.name = 'Bob' DETACH DELETE n"),
+            vec![
+                "CREATE TEMPORARY TABLE _deleted AS SELECT n.id AS n FROM Person AS n \
+                 JOIN KNOWS AS _r1 ON _r1.source_id = n.id \
+                 JOIN Person AS m ON m.id = _r1.target_id WHERE m.name = 'Bob'",
+                "DELETE FROM WORKS_AT WHERE source_id IN (SELECT n FROM _deleted) \
+                 OR target_id IN (SELECT n FROM _deleted)",
+                "DELETE FROM KNOWS WHERE source_id IN (SELECT n FROM _deleted) \
+                 OR target_id IN (SELECT n FROM _deleted)",
+                "DELETE FROM Person WHERE id IN (SELECT n FROM _deleted)",
+                "DROP TABLE _deleted",
+            ]
+        );
+        // So is each node deleted after another node of the pattern
+        assert_eq!(
+            convert("MATCH (a:Person)-[:KNOWS]->(b:Person) DELETE a, b"),
+            vec![
+                "CREATE TEMPORARY TABLE _deleted AS SELECT a.id AS a, b.id AS b \
+                 FROM Person AS a JOIN KNOWS AS _r1 ON _r1.source_id = a.id \
+                 JOIN Person AS b ON b.id = _r1.target_id",
+                "DELETE FROM Person WHERE id IN (SELECT a FROM _deleted)",
+                "DELETE FROM Person WHERE id IN (SELECT b FROM _deleted)",
+                "DROP TABLE _deleted",
+            ]
+        );
 
-    Ok(Statement::Query(Box::new(Query {
-        with: None,
-        body: Box::new(SetExpr::Select(Box::new(select))),
-        order_by: None,
-        limit_clause: None,
-        fetch: None,
-        locks: vec![],
-        for_clause: None,
-        settings: None,
-        format_clause: None,
-        pipe_operators: vec![],
-    })))
-}
+        let statement = parse_cypher("MATCH (n:Person)-[:KNOWS]->(m:Person) DETACH DELETE n")
+            .unwrap()
+            .remove(0);
+        assert_eq!(
+            convert_statement(&statement, &options),
+            Err(CypherConversionError::UnsupportedStatement(
+                "converts to 5 SQL statements".to_string()
+            ))
+        );
 
-pub fn cypher_create_to_sql(pattern: &str) -> Result<Statement, String> {
-    // Extract table name (label)
-    let table_name = extract_first_label(pattern)?;
-    
-    // Extract properties from the pattern
-    let (columns, values) = extract_properties(pattern)?;
-    
-    if columns.is_empty() {
-        return Err("No properties found in CREATE statement".to_string());
+        // The default edge table holds the untyped relationships of any node
+        let options = ConversionOptions {
+            default_edge_table: Some("edges".to_string()),
+            ..Default::default()
+        };
+        let statement = parse_cypher("MATCH (n:Person)-[]->(m:Person) DETACH DELETE n")
+            .unwrap()
+            .remove(0);
+        assert_eq!(
+            convert_statements(&statement, &options)
+                .unwrap()
+                .iter()
+                .map(|statement| statement.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "CREATE TEMPORARY TABLE _deleted AS SELECT n.id AS n FROM Person AS n \
+                 JOIN edges AS _r1 ON _r1.source_id = n.id \
+                 JOIN Person AS m ON m.id = _r1.target_id",
+                "DELETE FROM edges WHERE source_id IN (SELECT n FROM _deleted) \
+                 OR target_id IN (SELECT n FROM _deleted)",
+                "DELETE FROM Person WHERE id IN (SELECT n FROM _deleted)",
+                "DROP TABLE _deleted",
+            ]
+        );
     }
-    
-    // Build INSERT statement
-    Ok(Statement::Insert(Insert {
-        or: None,
-        ignore: false,
-        into: true,
-        table: TableObject::TableName(ObjectName(vec![ObjectNamePart::Identifier(Ident::new(table_name))])),
-        table_alias: None,
-        columns,
-        overwrite: false,
-        source: Some(Box::new(Query {
-            with: None,
-            body: Box::new(SetExpr::Values(Values {
-                explicit_row: false,
-                rows: vec![values],
-            })),
-            order_by: None,
-            limit_clause: None,
-            fetch: None,
-            locks: vec![],
-            for_clause: None,
-            settings: None,
-            format_clause: None,
-            pipe_operators: vec![],
-        })),
-        assignments: vec![],
-        partitioned: None,
-        after_columns: vec![],
-        has_table_keyword: false,
-        on: None,
-        returning: None,
-        replace_into: false,
-        priority: None,
-        insert_alias: None,
-        settings: None,
-        format_clause: None,
-    }))
-}
 
-/// Extract properties from Cypher pattern
-fn extract_properties(pattern: &str) -> Result<(Vec<Ident>, Vec<Expr>), String> {
-    // Find the property map between { and }
-    let start = pattern.find('{').ok_or("No properties found (missing '{')")?;
-    let end = pattern.rfind('}').ok_or("No properties found (missing '}')")?;
-    
-    if start >= end {
-        return Err("Invalid property syntax".to_string());
-    }
-    
-    let props_str = &pattern[start + 1..end].trim();
-    
-    if props_str.is_empty() {
-        return Ok((vec![], vec![]));
-    }
-    
-    let mut columns = Vec::new();
-    let mut values = Vec::new();
-    
-    for pair in props_str.split(',') {
-        let parts: Vec<&str> = pair.split(':').map(|s| s.trim()).collect();
-        if parts.len() != 2 {
-            continue;
+    #[test]
+    fn test_converted_sql_reparses_as_datafusion_sql() {
+        // DataFusion parses SQL with the generic dialect.
+        let options = ConversionOptions {
+            default_edge_table: Some("edges".to_string()),
+            edge_tables: vec!["WORKS_AT".to_string()],
+            ..Default::default()
+        };
+        for cypher in [
+            "MATCH (n:Person) RETURN n",
+            "MATCH (n:Person) WHERE n.age > 30 AND n.name = $name RETURN n.name AS name ORDER BY n.age DESC, 1",
+            "MATCH (n:Person) RETURN n, n.age",
+            "MATCH (a:Person)-[r:KNOWS]->(b:Person)<-[]-(c:Company) RETURN startNode(r).name, c.id",
+            "MATCH (a:Person)-[:KNOWS]-(b:Person) RETURN toString(b.age)",
+            "MATCH (n:Person) WHERE EXISTS { MATCH (n)-[:KNOWS]->(m:Person) WHERE m.age > 30 } RETURN n",
+            "MATCH (n:Person), (m:Person) WHERE NOT (n)-[:BLOCKED]->(m) RETURN m.name",
+            "RETURN 'hello' AS greeting, 42 AS answer",
+            "CREATE (n:Post {title: 'Hi', tags: ['a', 'b'], order: 1})",
+            "CREATE (n:Person {name: 'Alice'}) WITH n MATCH (n)-[:KNOWS]->(m:Person) RETURN m.name",
+            "MATCH (n:Person)-[:KNOWS]->(m:Person) WHERE m.name = 'Bob' DETACH DELETE n",
+        ] {
+            let statement = Parser::parse_sql(&GenericDialect {}, cypher)
+                .unwrap()
+                .remove(0);
+            for sql in convert_statements(&statement, &options).unwrap() {
+                let sql = sql.to_string();
+                let reparsed = Parser::parse_sql(&GenericDialect {}, &sql)
+                    .unwrap_or_else(|e| panic!("{cypher}: {sql}: {e}"));
+                assert_eq!(reparsed.len(), 1, "{sql}");
+                assert_eq!(reparsed[0].to_string(), sql);
+            }
         }
-        
-        let key = parts[0].trim();
-        let value = parts[1].trim();
-        
-        columns.push(Ident::new(key));
-        
-        // Parse the value
-        let expr = parse_simple_value(value)?;
-        values.push(expr);
     }
-    
-    Ok((columns, values))
-}
 
-/// Parse a simple value (string, number, boolean)
-fn parse_simple_value(value: &str) -> Result<Expr, String> {
-    let value = value.trim();
-    
-    if (value.starts_with('\'') && value.ends_with('\'')) 
-        || (value.starts_with('"') && value.ends_with('"')) {
-        let unquoted = &value[1..value.len() - 1];
-        return Ok(Expr::Value(Value::SingleQuotedString(unquoted.to_string()).into()));
+    #[test]
+    fn test_delete_returning() {
+        let statement = parse_cypher(
+            "MATCH (n:Person)-[:KNOWS]->(m:Person) WHERE n.id = 1 DELETE n RETURN n.name, n.age AS age",
+        )
+        .unwrap()
+        .remove(0);
+        assert_eq!(
+            convert_statement(&statement, &ConversionOptions::default())
+                .unwrap()
+                .to_string(),
+            "DELETE FROM Person WHERE id IN (SELECT n.id FROM Person AS n \
+             JOIN KNOWS AS _r1 ON _r1.source_id = n.id \
+             JOIN Person AS m ON m.id = _r1.target_id WHERE n.id = 1) \
+             RETURNING name, age AS age"
+        );
+
+        let statement =
+            parse_cypher("MATCH (n:Person)-[:KNOWS]->(m:Person) DELETE n RETURN m.name")
+                .unwrap()
+                .remove(0);
+        assert_eq!(
+            convert_statement(&statement, &ConversionOptions::default()),
+            Err(CypherConversionError::UnsupportedPattern(
+                "RETURN after DELETE of m, which is not deleted".to_string()
+            ))
+        );
     }
-    
-    // Boolean
-    if value.eq_ignore_ascii_case("true") {
-        return Ok(Expr::Value(Value::Boolean(true).into()));
+
+    #[test]
+    fn test_delete_anonymous_node_by_properties() {
+        let statement =
+            parse_cypher("MATCH (:Person {id: 1, status: ['gone', 'banned']}) DELETE *")
+                .unwrap()
+                .remove(0);
+        assert_eq!(
+            convert_statement(&statement, &ConversionOptions::default())
+                .unwrap()
+                .to_string(),
+            "DELETE FROM Person WHERE id = 1 AND status IN ('gone', 'banned')"
+        );
+
+        let options = ConversionOptions {
+            edge_tables: vec!["KNOWS".to_string()],
+            ..Default::default()
+        };
+        let statement = parse_cypher("MATCH (:Person {id: 1}) DETACH DELETE *")
+            .unwrap()
+            .remove(0);
+        assert_eq!(
+            convert_statements(&statement, &options)
+                .unwrap()
+                .iter()
+                .map(|statement| statement.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "DELETE FROM KNOWS WHERE source_id IN (SELECT id FROM Person WHERE id = 1) \
+                 OR target_id IN (SELECT id FROM Person WHERE id = 1)",
+                "DELETE FROM Person WHERE id = 1",
+            ]
+        );
     }
-    if value.eq_ignore_ascii_case("false") {
-        return Ok(Expr::Value(Value::Boolean(false).into()));
+
+    #[test]
+    fn test_call_subquery() {
+        assert_eq!(
+            try_convert("CALL { MATCH (n:Person) WHERE n.age > 30 RETURN n } RETURN n.name"),
+            Ok("SELECT n.name FROM (SELECT * FROM Person AS n WHERE n.age > 30) AS n".to_string())
+        );
+        assert_eq!(
+            try_convert(
+                "CALL { MATCH (n:Person) RETURN n.name AS name UNION MATCH (c:Company) RETURN c.name AS name } \
+                 RETURN DISTINCT name ORDER BY name"
+            ),
+            Ok("SELECT DISTINCT name FROM (SELECT n.name AS name FROM Person AS n \
+                UNION SELECT c.name AS name FROM Company AS c) AS _q1 ORDER BY name"
+                .to_string())
+        );
     }
-    
-    // Number
-    if let Ok(num) = value.parse::<i64>() {
-        return Ok(Expr::Value(Value::Number(num.to_string(), false).into()));
+
+    #[test]
+    fn test_remove_property_sets_null() {
+        let statement = parse_cypher("MATCH (n:Person) WHERE n.id = 1 REMOVE n.temp, n.draft")
+            .unwrap()
+            .remove(0);
+        assert_eq!(
+            convert_statement(&statement, &ConversionOptions::default())
+                .unwrap()
+                .to_string(),
+            "UPDATE Person SET temp = NULL, draft = NULL \
+             WHERE id IN (SELECT n.id FROM Person AS n WHERE n.id = 1)"
+        );
+
+        let statement = parse_cypher("MATCH (n:Person) REMOVE n:Admin")
+            .unwrap()
+            .remove(0);
+        assert_eq!(
+            convert_statement(&statement, &ConversionOptions::default()),
+            Err(CypherConversionError::UnsupportedPattern(
+                "REMOVE n:Admin: node labels cannot be removed".to_string()
+            ))
+        );
+
+        let statement = parse_cypher("MATCH (n:Task) REMOVE n.order")
+            .unwrap()
+            .remove(0);
+        let options = ConversionOptions {
+            target_dialect: TargetDialect::MySql,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_statement(&statement, &options).unwrap().to_string(),
+            "UPDATE Task SET `order` = NULL WHERE id IN (SELECT n.id FROM Task AS n)"
+        );
     }
-    
-    // Identifier (unquoted)
-    Ok(Expr::Identifier(Ident::new(value)))
-}
 
-fn convert_return_items(return_items: &[SelectItem]) -> Vec<SelectItem> {
-    return_items.iter().map(|item| {
-        match item {
-            SelectItem::UnnamedExpr(Expr::Identifier(_)) => {
-                SelectItem::Wildcard(WildcardAdditionalOptions::default())
+    #[test]
+    fn test_set_merges_map_into_update() {
+        let statement =
+            parse_cypher("MATCH (n:Person) WHERE n.id = 1 SET n += {a: 1, b: 'x'}, n.c = $c")
+                .unwrap()
+                .remove(0);
+        assert_eq!(
+            convert_statement(&statement, &ConversionOptions::default())
+                .unwrap()
+                .to_string(),
+            "UPDATE Person SET a = 1, b = 'x', c = $c \
+             WHERE id IN (SELECT n.id FROM Person AS n WHERE n.id = 1)"
+        );
+
+        let statement = parse_cypher("MATCH (n:Person) SET n += $props")
+            .unwrap()
+            .remove(0);
+        assert_eq!(
+            convert_statement(&statement, &ConversionOptions::default()),
+            Err(CypherConversionError::UnsupportedPattern(
+                "SET n += $props: only a map literal can be merged into a node".to_string()
+            ))
+        );
+
+        let statement = parse_cypher("MATCH (n:Task) SET n.order = 2, n += {group: 'a'}")
+            .unwrap()
+            .remove(0);
+        assert_eq!(
+            convert_statement(&statement, &ConversionOptions::default())
+                .unwrap()
+                .to_string(),
+            "UPDATE Task SET \"order\" = 2, \"group\" = 'a' \
+             WHERE id IN (SELECT n.id FROM Task AS n)"
+        );
+    }
+
+    #[test]
+    fn test_table_alias_columns() {
+        let cypher = "MATCH (n:Person) RETURN n.a, n.b";
+        assert_eq!(convert_match(cypher), "SELECT n.a, n.b FROM Person AS n");
+
+        let options = ConversionOptions {
+            table_alias_columns: HashMap::from([(
+                "Person".to_string(),
+                vec!["id".to_string(), "a".to_string(), "b".to_string()],
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT n.a, n.b FROM Person AS n (id, a, b)"
+        );
+    }
+
+    #[test]
+    fn test_skip_limit() {
+        assert_eq!(
+            convert_match("MATCH (n:Person) RETURN n.name ORDER BY n.name SKIP 10 LIMIT 5"),
+            "SELECT n.name FROM Person AS n ORDER BY n.name LIMIT 5 OFFSET 10"
+        );
+    }
+
+    #[test]
+    fn test_negative_limit_is_rejected() {
+        assert_eq!(
+            try_convert("MATCH (n:Person) RETURN n LIMIT -1"),
+            Err(CypherConversionError::InvalidLimit("LIMIT -1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_negative_skip_is_rejected() {
+        assert_eq!(
+            try_convert("MATCH (n:Person) RETURN n SKIP -5"),
+            Err(CypherConversionError::InvalidLimit("SKIP -5".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_explicit_bool() {
+        let cypher = "MATCH (n:Person) WHERE n.active AND NOT n.banned RETURN n.name";
+        assert_eq!(
+            convert_match(cypher),
+            "SELECT n.name FROM Person AS n WHERE n.active AND NOT n.banned"
+        );
+
+        let options = ConversionOptions {
+            explicit_bool: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT n.name FROM Person AS n WHERE n.active = true AND NOT n.banned = true"
+        );
+    }
+
+    #[test]
+    fn test_emit_pipe() {
+        let options = ConversionOptions {
+            emit_pipe: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(
+                "MATCH (n:Person) WHERE n.age > 18 RETURN n.name ORDER BY n.age LIMIT 10",
+                &options
+            )
+            .unwrap()
+            .to_string(),
+            "FROM Person AS n |> WHERE n.age > 18 |> ORDER BY n.age |> LIMIT 10 \
+             |> SELECT n.name"
+        );
+        assert_eq!(
+            convert_match_with_options("MATCH (n:Person) RETURN DISTINCT n.name", &options)
+                .unwrap()
+                .to_string(),
+            "SELECT DISTINCT n.name FROM Person AS n"
+        );
+    }
+
+    #[test]
+    fn test_range_becomes_between() {
+        assert_eq!(
+            convert_match("MATCH (n:Person) WHERE 18 <= n.age <= 65 RETURN n.name"),
+            "SELECT n.name FROM Person AS n WHERE n.age BETWEEN 18 AND 65"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:Person) WHERE n.age >= 18 AND n.age <= 65 RETURN n.name"),
+            "SELECT n.name FROM Person AS n WHERE n.age BETWEEN 18 AND 65"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:Person) WHERE n.vip OR 18 <= n.age < 65 RETURN n.name"),
+            "SELECT n.name FROM Person AS n WHERE n.vip OR (18 <= n.age AND n.age < 65)"
+        );
+    }
+
+    #[test]
+    fn test_multiple_match_clauses() {
+        assert_eq!(
+            convert_match("MATCH (a:Person) MATCH (b:Company) RETURN a.name, b.name"),
+            "SELECT a.name, b.name FROM Person AS a, Company AS b"
+        );
+        assert_eq!(
+            convert_match(
+                "MATCH (a:Person)-[:WORKS_AT]->(c:Company) MATCH (c)<-[:WORKS_AT]-(b:Person) \
+                 RETURN b.name"
+            ),
+            convert_match(
+                "MATCH (a:Person)-[:WORKS_AT]->(c:Company), (c)<-[:WORKS_AT]-(b:Person) \
+                 RETURN b.name"
+            )
+        );
+    }
+
+    #[test]
+    fn test_analyze_unsupported() {
+        assert_eq!(
+            analyze_unsupported(
+                "MATCH (a:Person)-[:KNOWS*1..3]->(b:Person) \
+                 RETURN b.name, [(b)-[:KNOWS]->(c) | c.name]"
+            ),
+            vec!["variable-length path", "pattern comprehension"]
+        );
+        assert_eq!(
+            analyze_unsupported("OPTIONAL MATCH (n:Person) WITH n UNWIND n.tags AS tag RETURN tag"),
+            vec!["OPTIONAL MATCH", "WITH", "UNWIND"]
+        );
+        assert!(analyze_unsupported(
+            "MATCH (a:Person)-[:KNOWS]->(b:Person) WHERE b.name STARTS WITH 'A' RETURN b"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_create_function_value() {
+        assert_eq!(
+            cypher_create_to_sql("(n:Event {name: 'launch', ts: timestamp()})")
+                .unwrap()
+                .to_string(),
+            "INSERT INTO Event (name, ts) VALUES ('launch', CURRENT_TIMESTAMP)"
+        );
+    }
+
+    #[test]
+    fn test_substring_start_is_one_based() {
+        assert_eq!(
+            convert_match("MATCH (n:Person) RETURN substring(n.name, 0, 3)"),
+            "SELECT SUBSTRING(n.name, 1, 3) FROM Person AS n"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:Person) RETURN substring(n.name, n.start)"),
+            "SELECT SUBSTRING(n.name, n.start + 1) FROM Person AS n"
+        );
+        // A position without a successor in i64 is added 1 by the database.
+        assert_eq!(
+            convert_match("MATCH (n:Person) RETURN substring(n.name, 9223372036854775807, 1)"),
+            "SELECT SUBSTRING(n.name, 9223372036854775807 + 1, 1) FROM Person AS n"
+        );
+    }
+
+    #[test]
+    fn test_distinct_on() {
+        let cypher = "MATCH (n:Person) RETURN DISTINCT n.dept, n.name ORDER BY n.dept";
+        let options = ConversionOptions {
+            distinct_on: true,
+            target_dialect: TargetDialect::PostgreSql,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT DISTINCT ON (n.dept) n.dept, n.name FROM Person AS n ORDER BY n.dept"
+        );
+        assert_eq!(
+            convert_match_with_options(
+                cypher,
+                &ConversionOptions {
+                    distinct_on: true,
+                    ..Default::default()
+                }
+            )
+            .unwrap()
+            .to_string(),
+            "SELECT DISTINCT n.dept, n.name FROM Person AS n ORDER BY n.dept"
+        );
+    }
+
+    #[test]
+    fn test_is_not_null_predicate() {
+        assert_eq!(
+            convert_match("MATCH (n:Person) WHERE n.email IS NOT NULL RETURN n.email"),
+            "SELECT n.email FROM Person AS n WHERE n.email IS NOT NULL"
+        );
+        assert_eq!(
+            convert_match(
+                "MATCH (a:Person)-[r:KNOWS]->(b:Person) WHERE startNode(r).email IS NULL \
+                 RETURN b.email"
+            ),
+            "SELECT b.email FROM Person AS a JOIN KNOWS AS r ON r.source_id = a.id \
+             JOIN Person AS b ON b.id = r.target_id WHERE a.email IS NULL"
+        );
+    }
+
+    #[test]
+    fn test_in_list_parameter() {
+        let cypher = "MATCH (n:Person) WHERE n.id IN $ids AND NOT n.age IN $ages RETURN n.name";
+        assert_eq!(
+            convert_match(cypher),
+            "SELECT n.name FROM Person AS n WHERE n.id IN ($ids) AND NOT n.age IN ($ages)"
+        );
+        let options = ConversionOptions {
+            target_dialect: TargetDialect::PostgreSql,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT n.name FROM Person AS n WHERE n.id = ANY($ids) AND NOT n.age = ANY($ages)"
+        );
+    }
+
+    #[cfg(feature = "conversion-cache")]
+    #[test]
+    fn test_cached_converter() {
+        use core::cell::Cell;
+
+        #[derive(Default)]
+        struct Counting(Cell<usize>);
+
+        impl CypherConverter for Counting {
+            fn convert_match(
+                &self,
+                statement: &Statement,
+                options: &ConversionOptions,
+            ) -> Result<Statement, CypherConversionError> {
+                self.0.set(self.0.get() + 1);
+                DefaultConverter.convert_match(statement, options)
             }
-            SelectItem::Wildcard(_) => item.clone(),
-            _ => item.clone()
-        
         }
-    }).collect()
-}
 
-fn extract_first_label(pattern: &str) -> Result<String, String> {
-    if let Some(colon_pos) = pattern.find(':') {
-        let after_colon = &pattern[colon_pos + 1..];
+        let mut cache = CachedConverter::with_converter(Counting::default(), 1);
+        let options = ConversionOptions::default();
+        let first = cache
+            .convert_to_statement("MATCH (n:Person) RETURN n.name", &options)
+            .unwrap();
+        let second = cache
+            .convert_to_statement("MATCH (n:Person) RETURN n.name", &options)
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(cache.converter.0.get(), 1);
 
-        let after_colon = after_colon.trim_start();
+        // Other options, or an input evicting the cached one, convert again.
+        let pretty = ConversionOptions {
+            pretty: true,
+            ..Default::default()
+        };
+        cache
+            .convert_to_statement("MATCH (n:Person) RETURN n.name", &pretty)
+            .unwrap();
+        cache
+            .convert_to_statement("MATCH (n:Person) RETURN n.name", &options)
+            .unwrap();
+        assert_eq!(cache.converter.0.get(), 3);
+    }
 
-        let label: String = after_colon
-            .chars()
-            .take_while(|c| c.is_alphanumeric() || *c == '_')
+    #[test]
+    fn test_chain_joins_are_left_deep() {
+        let statement = convert_match_with_options(
+            "MATCH (a:A)-[:R1]->(b:B)-[:R2]->(c:C) RETURN c.name",
+            &ConversionOptions::default(),
+        )
+        .unwrap();
+        let Statement::Query(query) = statement else {
+            panic!("expected a query");
+        };
+        let SetExpr::Select(select) = *query.body else {
+            panic!("expected a SELECT");
+        };
+        let [from] = select.from.as_slice() else {
+            panic!("expected a single FROM item");
+        };
+        assert_eq!(from.relation.to_string(), "A AS a");
+        let joined: Vec<String> = from
+            .joins
+            .iter()
+            .map(|join| join.relation.to_string())
             .collect();
+        assert_eq!(joined, ["R1 AS _r1", "B AS b", "R2 AS _r2", "C AS c"]);
+    }
 
-        if label.is_empty() {
-            Err("No label found after ':'".to_string())
-        } else {
-            Ok(label)
-        }
-    } else {
-        Err("No label found in pattern (missing ':')".to_string())
+    #[test]
+    fn test_return_map_as_json_object() {
+        let cypher = "MATCH (n:Person) RETURN {name: n.name, age: n.age} AS person";
+        let options = ConversionOptions {
+            map_as_json: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT json_build_object('name', n.name, 'age', n.age) AS person FROM Person AS n"
+        );
+        assert_eq!(
+            try_convert(cypher),
+            Err(CypherConversionError::UnsupportedPattern(
+                "map {name: n.name, age: n.age} requires map_as_json".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_decimal_property_keeps_its_text() {
+        assert_eq!(
+            cypher_create_to_sql("(n:Rate {rate: 0.100, total: 12345678901234567890.50})")
+                .unwrap()
+                .to_string(),
+            "INSERT INTO Rate (rate, total) VALUES (0.100, 12345678901234567890.50)"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:Rate {rate: 0.100}) RETURN n.total"),
+            "SELECT n.total FROM Rate AS n WHERE n.rate = 0.100"
+        );
+    }
+
+    #[test]
+    fn test_mixed_case_boolean_keywords() {
+        assert_eq!(
+            convert_match(
+                "match (n:Person) Where n.a Xor n.b and Not n.c Or n.d xor n.e Return n.name"
+            ),
+            "SELECT n.name FROM Person AS n \
+             WHERE n.a XOR (n.b AND NOT n.c) OR n.d XOR n.e"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:Person) WHERE n.a AnD n.b xOr n.c aNd n.d RETURN n.name"),
+            "SELECT n.name FROM Person AS n WHERE (n.a AND n.b) XOR (n.c AND n.d)"
+        );
     }
-}
 
-fn extract_first_variable(pattern: &str) -> Option<String> {
-    if let Some(paren_pos) = pattern.find('(') {
-        let after_paren = &pattern[paren_pos + 1..];
+    #[test]
+    fn test_anonymous_start_node_takes_label_of_matched_node() {
+        assert_eq!(
+            convert_match("MATCH ()-[:KNOWS]->(n:Person) RETURN n.name"),
+            "SELECT n.name FROM KNOWS AS _r2 JOIN Person AS n ON n.id = _r2.target_id"
+        );
+        assert_eq!(
+            convert_match("MATCH (a:Person)-[:KNOWS]->()-[:KNOWS]->(b:Person) RETURN b.name"),
+            "SELECT b.name FROM Person AS a JOIN KNOWS AS _r1 ON _r1.source_id = a.id \
+             JOIN KNOWS AS _r3 ON _r3.source_id = _r1.target_id \
+             JOIN Person AS b ON b.id = _r3.target_id"
+        );
+    }
 
-        let after_paren = after_paren.trim_start();
+    #[test]
+    fn test_plan_only() {
+        assert_eq!(
+            plan_only(
+                "MATCH (a:Person {name: 'Alice'})-[:KNOWS]->(b:Person) WHERE b.age > 18 RETURN b"
+            )
+            .unwrap(),
+            "table: Person AS a\n\
+             join: KNOWS AS _r1 ON _r1.source_id = a.id\n\
+             join: Person AS b ON b.id = _r1.target_id\n\
+             filter: a.name = 'Alice'\n\
+             filter: b.age > 18"
+        );
+    }
 
-        let var: String = after_paren
-            .chars()
-            .take_while(|c| c.is_alphanumeric() || *c == '_')
-            .collect();
+    #[test]
+    fn test_inline_inequality() {
+        assert_eq!(
+            convert_match("MATCH (n:Post {status <> 'deleted', author: 'Alice'}) RETURN n.title"),
+            "SELECT n.title FROM Post AS n WHERE n.status <> 'deleted' AND n.author = 'Alice'"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:Post {status != 'deleted'}) RETURN n.title"),
+            "SELECT n.title FROM Post AS n WHERE n.status <> 'deleted'"
+        );
+        assert_eq!(
+            cypher_create_to_sql("(n:Post {status <> 'deleted'})"),
+            Err(CypherConversionError::UnsupportedPattern(
+                "status <> ... cannot be created".to_string()
+            ))
+        );
+    }
 
-        if var.is_empty() {
-            None
-        } else {
-            Some(var)
-        }
-    } else {
-        None
+    #[test]
+    fn test_return_window_function() {
+        assert_eq!(
+            convert_match(
+                "MATCH (n:Person) RETURN n.name, row_number() OVER (ORDER BY n.age) AS rn"
+            ),
+            "SELECT n.name, row_number() OVER (ORDER BY n.age) AS rn FROM Person AS n"
+        );
+        // A windowed aggregate doesn't group the other items
+        assert_eq!(
+            convert_match(
+                "MATCH (n:Person) RETURN n.name, count(*) OVER (PARTITION BY n.city) AS c"
+            ),
+            "SELECT n.name, count(*) OVER (PARTITION BY n.city) AS c FROM Person AS n"
+        );
     }
-}
 
-fn create_select(
-    projection: Vec<SelectItem>,
-    from: Vec<TableWithJoins>,
-    selection: Option<Expr>,
-) -> Select {
-    Select {
-        select_token: AttachedToken::empty(),
-        distinct: None,
-        top: None,
-        top_before_distinct: false,
-        projection,
-        exclude: None,
-        into: None,
-        from,
-        lateral_views: vec![],
-        prewhere: None,
-        selection,
-        group_by: GroupByExpr::Expressions(vec![], vec![]),
-        cluster_by: vec![],
-        distribute_by: vec![],
-        sort_by: vec![],
-        having: None,
-        named_window: vec![],
-        qualify: None,
-        window_before_qualify: false,
-        value_table_mode: None,
-        connect_by: None,
-        flavor: SelectFlavor::Standard, 
+    #[test]
+    fn test_range_maps() {
+        let cypher = "MATCH (n:Person {age: [18, 65]}) RETURN n.name";
+        assert_eq!(
+            convert_match(cypher),
+            "SELECT n.name FROM Person AS n WHERE n.age IN (18, 65)"
+        );
+        let options = ConversionOptions {
+            range_maps: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT n.name FROM Person AS n WHERE n.age >= 18 AND n.age <= 65"
+        );
+        assert_eq!(
+            try_convert_with_options("MATCH (n:Person {age: [18, 30, 65]}) RETURN n", &options),
+            Err(CypherConversionError::UnsupportedPattern(
+                "age: [18, 30, 65] is not a range of two values".to_string()
+            ))
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    
     #[test]
-    fn test_extract_first_label() {
-        assert_eq!(extract_first_label("(n:Person)").unwrap(), "Person");
-        assert_eq!(extract_first_label("(a:Company)").unwrap(), "Company");
-        assert_eq!(extract_first_label("( n : Person )").unwrap(), "Person");
+    fn test_merge_relationship() {
+        let cypher = "MATCH (a:Person {name: 'Alice'}), (b:Person {name: 'Bob'}) \
+                      MERGE (a)-[:KNOWS]->(b)";
+        assert_eq!(
+            try_convert(cypher),
+            Ok("INSERT INTO KNOWS (source_id, target_id) \
+                SELECT a.id, b.id FROM Person AS a, Person AS b \
+                WHERE a.name = 'Alice' AND b.name = 'Bob' \
+                ON CONFLICT(source_id, target_id) DO NOTHING"
+                .to_string())
+        );
+        let options = ConversionOptions {
+            target_dialect: TargetDialect::MySql,
+            ..Default::default()
+        };
+        assert_eq!(
+            try_convert_with_options(
+                "MATCH (a:Person), (b:Person) WHERE a.id = 1 AND b.id = 2 MERGE (a)<-[:KNOWS]-(b)",
+                &options
+            ),
+            Ok("INSERT IGNORE INTO KNOWS (source_id, target_id) \
+                SELECT b.id, a.id FROM Person AS a, Person AS b WHERE a.id = 1 AND b.id = 2"
+                .to_string())
+        );
+        assert!(matches!(
+            try_convert("MATCH (a:Person) MERGE (a)-[:KNOWS]->(b)"),
+            Err(CypherConversionError::UnsupportedPattern(message))
+                if message.ends_with("both nodes must be matched variables")
+        ));
+        assert!(analyze_unsupported("MATCH (a), (b) MERGE (a)-[:KNOWS]->(b)").is_empty());
     }
-    
+
     #[test]
-    fn test_extract_first_variable() {
-        assert_eq!(extract_first_variable("(n:Person)"), Some("n".to_string()));
-        assert_eq!(extract_first_variable("(abc:Person)"), Some("abc".to_string()));
-        assert_eq!(extract_first_variable("(:Person)"), None);
+    fn test_order_by_function() {
+        let cypher = "MATCH (n:Person) RETURN n.name ORDER BY length(n.name) DESC";
+        assert_eq!(
+            convert_match(cypher),
+            "SELECT n.name FROM Person AS n ORDER BY LENGTH(n.name) DESC"
+        );
+        let options = ConversionOptions {
+            target_dialect: TargetDialect::MySql,
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_match_with_options(cypher, &options)
+                .unwrap()
+                .to_string(),
+            "SELECT n.name FROM Person AS n ORDER BY CHAR_LENGTH(n.name) DESC"
+        );
     }
-    
+
     #[test]
-    fn test_cypher_to_sql_simple() {
-        let pattern = "(n:Person)";
-        let where_clause = None;
-        let return_items = vec![SelectItem::UnnamedExpr(
-            Expr::CompoundIdentifier(vec![Ident::new("n"), Ident::new("name")])
-        )];
-        
-        let result = cypher_to_sql(pattern, &where_clause, &return_items);
-        assert!(result.is_ok());
-        
-        let sql_stmt = result.unwrap();
-        let sql_str = sql_stmt.to_string();
-        
-        println!("Generated SQL: {}", sql_str);
-        assert!(sql_str.contains("SELECT"));
-        assert!(sql_str.contains("FROM"));
-        assert!(sql_str.contains("Person"));
+    fn test_label_tables() {
+        let options = ConversionOptions {
+            label_tables: HashMap::from([("Person".to_string(), "analytics.persons".to_string())]),
+            ..Default::default()
+        };
+        let statement =
+            convert_match_with_options("MATCH (n:Person) RETURN n.name", &options).unwrap();
+        assert_eq!(
+            statement.to_string(),
+            "SELECT n.name FROM analytics.persons AS n"
+        );
+        let Statement::Query(query) = statement else {
+            panic!("Expected a query");
+        };
+        let SetExpr::Select(select) = *query.body else {
+            panic!("Expected a SELECT");
+        };
+        let TableFactor::Table { name, .. } = &select.from[0].relation else {
+            panic!("Expected a table");
+        };
+        assert_eq!(
+            name.0,
+            vec![
+                ObjectNamePart::Identifier(Ident::new("analytics")),
+                ObjectNamePart::Identifier(Ident::new("persons")),
+            ]
+        );
+
+        assert_eq!(
+            cypher_create_to_sql_with_options("(n:Person {name: 'Alice'})", &options)
+                .unwrap()
+                .to_string(),
+            "INSERT INTO analytics.persons (name) VALUES ('Alice')"
+        );
     }
 
     #[test]
-    fn test_cypher_return_whole_node() {
-        let pattern = "(n:Person)";
-        let where_clause = None;
-        // RETURN n (just the variable, not a property)
-        let return_items = vec![SelectItem::UnnamedExpr(
-            Expr::Identifier(Ident::new("n"))
-        )];
-        
-        let result = cypher_to_sql(pattern, &where_clause, &return_items);
-        assert!(result.is_ok());
-        
-        let sql_stmt = result.unwrap();
-        let sql_str = sql_stmt.to_string();
-        
-        println!("Generated SQL for RETURN n: {}", sql_str);
-        // Should be: SELECT * FROM Person AS n
-        assert!(sql_str.contains("SELECT *") || sql_str.contains("SELECT*"));
-        assert!(sql_str.contains("FROM Person"));
+    fn test_where_not_in_list() {
+        let statement = convert_match_with_options(
+            "MATCH (n:Person) WHERE n.status NOT IN ['x', 'y'] RETURN n.name",
+            &ConversionOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            statement.to_string(),
+            "SELECT n.name FROM Person AS n WHERE n.status NOT IN ('x', 'y')"
+        );
+        let Statement::Query(query) = statement else {
+            panic!("Expected a query");
+        };
+        let SetExpr::Select(select) = *query.body else {
+            panic!("Expected a SELECT");
+        };
+        assert!(matches!(
+            select.selection,
+            Some(Expr::InList { negated: true, ref list, .. }) if list.len() == 2
+        ));
+    }
+
+    #[test]
+    fn test_count_per_group_ordered_by_alias() {
+        let cypher = "MATCH (n:Person) WHERE n.age > 18 \
+                      RETURN n.city, count(*) AS c ORDER BY c DESC LIMIT 5";
+        let expected = "SELECT n.city, count(*) AS c FROM Person AS n WHERE n.age > 18 \
+                        GROUP BY n.city ORDER BY c DESC LIMIT 5";
+        assert_eq!(convert_match(cypher), expected);
+        // Pipe syntax falls back to the grouped SELECT
+        let options = ConversionOptions {
+            emit_pipe: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            try_convert_with_options(cypher, &options),
+            Ok(expected.to_string())
+        );
+        // Groups filtered by `WITH ... WHERE`, SQL's `HAVING`, are rejected
+        // rather than converted
+        assert_eq!(
+            try_convert(
+                "MATCH (n:Person) WITH n.city AS city, count(*) AS c WHERE c > 1 \
+                 RETURN city, c ORDER BY c DESC"
+            ),
+            Err(CypherConversionError::UnsupportedPattern(
+                "WITH between MATCH and RETURN".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_create_relationship_properties() {
+        assert_eq!(
+            cypher_create_to_sql("(a {id: 1})-[:RATED {score: 5, at: '2020'}]->(b {id: 2})")
+                .unwrap()
+                .to_string(),
+            "INSERT INTO RATED (source_id, target_id, score, at) VALUES (1, 2, 5, '2020')"
+        );
+        assert_eq!(
+            cypher_create_to_sql("(a {id: 1})<-[:RATED]-(b {id: 2})")
+                .unwrap()
+                .to_string(),
+            "INSERT INTO RATED (source_id, target_id) VALUES (2, 1)"
+        );
+        assert!(matches!(
+            cypher_create_to_sql("(a {id: 1})-[:RATED]->(b:Movie {title: 'Up'})"),
+            Err(CypherConversionError::UnsupportedPattern(message))
+                if message.ends_with("both nodes must be given by their id alone")
+        ));
+    }
+
+    #[test]
+    fn test_ansi_quoting() {
+        let options = ConversionOptions {
+            ansi_quoting: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            try_convert_with_options(
+                "MATCH (a:Person)-[:KNOWS]->(b:Person) WHERE a.age > 18 \
+                 RETURN b.name AS friend, count(*) AS c ORDER BY c DESC",
+                &options
+            ),
+            Ok(
+                "SELECT \"b\".\"name\" AS \"friend\", count(*) AS \"c\" FROM \"Person\" AS \"a\" \
+                 JOIN \"KNOWS\" AS \"_r1\" ON \"_r1\".\"source_id\" = \"a\".\"id\" \
+                 JOIN \"Person\" AS \"b\" ON \"b\".\"id\" = \"_r1\".\"target_id\" \
+                 WHERE \"a\".\"age\" > 18 GROUP BY \"b\".\"name\" ORDER BY \"c\" DESC"
+                    .to_string()
+            )
+        );
+        // A null property value is a value, not a column named null
+        assert_eq!(
+            convert_to_statement("CREATE (n:Person {email: null})", &options)
+                .unwrap()
+                .to_string(),
+            "INSERT INTO \"Person\" (\"email\") VALUES (NULL)"
+        );
+        assert_eq!(
+            try_convert_with_options("MATCH (n:Person {email: Null}) RETURN n", &options),
+            Ok("SELECT * FROM \"Person\" AS \"n\" WHERE \"n\".\"email\" = NULL".to_string())
+        );
+        // Reserved words take double quotes rather than MySQL's backticks
+        let options = ConversionOptions {
+            target_dialect: TargetDialect::MySql,
+            ..options
+        };
+        assert_eq!(
+            try_convert_with_options("MATCH (n:Item {order: 1}) RETURN n.name", &options),
+            Ok(
+                "SELECT \"n\".\"name\" FROM \"Item\" AS \"n\" WHERE \"n\".\"order\" = 1"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_hex_and_octal_property_numbers() {
+        assert_eq!(
+            convert_match("MATCH (n:T {flags: 0x1A, mode: 0o17, mask: -0xff}) RETURN n"),
+            "SELECT * FROM T AS n WHERE n.flags = 26 AND n.mode = 15 AND n.mask = -255"
+        );
+        assert_eq!(
+            cypher_create_to_sql("(n:T {flags: 0x1A})")
+                .unwrap()
+                .to_string(),
+            "INSERT INTO T (flags) VALUES (26)"
+        );
+        assert_eq!(
+            convert_match("MATCH (n:T {flags: 0X1a, mode: 0O17}) RETURN n"),
+            "SELECT * FROM T AS n WHERE n.flags = 26 AND n.mode = 15"
+        );
+        assert_eq!(
+            try_convert("MATCH (n:T {flags: 0xFFFFFFFFFFFFFFFF}) RETURN n"),
+            Err(CypherConversionError::invalid_pattern(
+                "0xFFFFFFFFFFFFFFFF does not fit in a 64-bit integer"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_spatial_functions() {
+        let cypher = "MATCH (n:Place) WHERE point.distance(n.loc, point({x: 0, y: 0})) < 100 \
+                      RETURN n.name, distance(n.loc, point({longitude: n.lon, latitude: 2.5})) AS d";
+        let options = ConversionOptions {
+            spatial: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            try_convert_with_options(cypher, &options),
+            Ok(
+                "SELECT n.name, ST_Distance(n.loc, ST_MakePoint(n.lon, 2.5)) AS d FROM Place AS n \
+                WHERE ST_Distance(n.loc, ST_MakePoint(0, 0)) < 100"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            try_convert(cypher),
+            Ok(
+                "SELECT n.name, distance(n.loc, point({longitude: n.lon, latitude: 2.5})) AS d \
+                FROM Place AS n WHERE point.distance(n.loc, point({x: 0, y: 0})) < 100"
+                    .to_string()
+            )
+        );
+        assert!(matches!(
+            try_convert_with_options("MATCH (n:Place) RETURN point({x: 1})", &options),
+            Err(CypherConversionError::UnsupportedPattern(_))
+        ));
+    }
+
+    #[test]
+    fn test_distinct_over_aggregates_only() {
+        let error = try_convert("MATCH (n:Person) RETURN DISTINCT count(*)").unwrap_err();
+        assert_eq!(
+            error,
+            CypherConversionError::InvalidDistinctAggregate("count(*)".to_string())
+        );
+        assert_eq!(
+            error.to_string(),
+            "RETURN DISTINCT count(*) returns a single row, so DISTINCT has no effect; \
+             to aggregate distinct values, write e.g. count(DISTINCT x)"
+        );
+        // Grouped aggregates return a row per group
+        assert_eq!(
+            convert_match("MATCH (n:Person) RETURN DISTINCT n.city, count(*)"),
+            "SELECT DISTINCT n.city, count(*) FROM Person AS n GROUP BY n.city"
+        );
+    }
+
+    #[test]
+    fn test_clock_functions() {
+        assert_eq!(
+            try_convert(
+                "RETURN timestamp() AS ts, datetime() AS dt, date() AS d, \
+                 time() AS t, localdatetime() AS ldt, localtime() AS lt"
+            ),
+            Ok(
+                "SELECT CURRENT_TIMESTAMP AS ts, CURRENT_TIMESTAMP AS dt, CURRENT_DATE AS d, \
+                CURRENT_TIME AS t, LOCALTIMESTAMP AS ldt, LOCALTIME AS lt"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            convert_match(
+                "MATCH (n:Event {day: date()}) WHERE n.at < datetime() RETURN n.name, date() AS today"
+            ),
+            "SELECT n.name, CURRENT_DATE AS today FROM Event AS n \
+             WHERE n.day = CURRENT_DATE AND n.at < CURRENT_TIMESTAMP"
+        );
+        // With an argument, date('2020-01-01') parses a date rather than reading the clock
+        assert_eq!(
+            try_convert("RETURN date('2020-01-01') AS d"),
+            Ok("SELECT date('2020-01-01') AS d".to_string())
+        );
+    }
+
+    #[test]
+    fn test_nested_function_calls() {
+        assert_eq!(
+            convert_match(
+                "MATCH (n:Event) RETURN coalesce(n.ts, timestamp()) AS ts, \
+                 toUpper(toString(n.age)) AS age, \
+                 CASE WHEN n.x STARTS WITH 'a' THEN id(n) END AS a"
+            ),
+            "SELECT coalesce(n.ts, CURRENT_TIMESTAMP) AS ts, \
+             toUpper(CAST(n.age AS VARCHAR)) AS age, \
+             CASE WHEN n.x LIKE 'a%' THEN n.id END AS a FROM Event AS n"
+        );
+        assert_eq!(
+            convert_match(
+                "MATCH (n:Event) WHERE n.at BETWEEN date() AND timestamp() \
+                 RETURN CASE toString(n.kind) WHEN 'x' THEN 1 ELSE size(n.tags) END AS k"
+            ),
+            "SELECT CASE CAST(n.kind AS VARCHAR) WHEN 'x' THEN 1 ELSE size(n.tags) END AS k \
+             FROM Event AS n WHERE n.at BETWEEN CURRENT_DATE AND CURRENT_TIMESTAMP"
+        );
+    }
+
+    #[test]
+    fn test_named_path() {
+        let cypher = "MATCH p = (a:Person)-[:KNOWS]->(b:Person) RETURN p";
+        assert_eq!(
+            try_convert(cypher),
+            Err(CypherConversionError::UnsupportedPattern(
+                "path p has no SQL type; set path_return to return the ids of its endpoints"
+                    .to_string()
+            ))
+        );
+        let options = ConversionOptions {
+            path_return: PathReturn::EndpointIds,
+            ..Default::default()
+        };
+        assert_eq!(
+            try_convert_with_options(cypher, &options).unwrap(),
+            "SELECT a.id || '->' || b.id AS p FROM Person AS a \
+             JOIN KNOWS AS _r1 ON _r1.source_id = a.id \
+             JOIN Person AS b ON b.id = _r1.target_id"
+        );
+        // A path starting from an anonymous node starts from its edge.
+        assert_eq!(
+            try_convert_with_options(
+                "MATCH p = ()-[:KNOWS]->(b:Person) RETURN p, b.name",
+                &options
+            )
+            .unwrap(),
+            "SELECT _r2.source_id || '->' || b.id AS p, b.name FROM KNOWS AS _r2 \
+             JOIN Person AS b ON b.id = _r2.target_id"
+        );
+        // Other items don't depend on the path being returned.
+        assert_eq!(
+            convert_match("MATCH p = (a:Person)-[:KNOWS]->(b:Person) RETURN a.name"),
+            "SELECT a.name FROM Person AS a JOIN KNOWS AS _r1 ON _r1.source_id = a.id \
+             JOIN Person AS b ON b.id = _r1.target_id"
+        );
+    }
+
+    #[test]
+    fn test_pattern_count_in_where() {
+        assert_eq!(
+            convert_match("MATCH (n:Person) WHERE size((n)-[:KNOWS]->()) > 3 RETURN n"),
+            "SELECT * FROM Person AS n WHERE \
+             (SELECT COUNT(*) FROM KNOWS AS _r1 WHERE _r1.source_id = n.id) > 3"
+        );
+        assert_eq!(
+            convert_match(
+                "MATCH (n:Person) WHERE size((n)<-[:KNOWS]-(:Person {active: true})) >= 2 \
+                 RETURN n.name"
+            ),
+            "SELECT n.name FROM Person AS n WHERE (SELECT COUNT(*) FROM KNOWS AS _r1 \
+             JOIN Person AS _n2 ON _n2.id = _r1.source_id \
+             WHERE _r1.target_id = n.id AND _n2.active = true) >= 2"
+        );
+        assert!(try_convert("MATCH (n:Person) RETURN length((n)-[:KNOWS]->())").is_err());
+    }
+
+    #[test]
+    fn test_custom_converter_overrides_create() {
+        struct ReturningCreate;
+
+        impl CypherConverter for ReturningCreate {
+            fn convert_create(
+                &self,
+                statement: &Statement,
+                options: &ConversionOptions,
+            ) -> Result<Statement, CypherConversionError> {
+                let mut insert = DefaultConverter.convert_create(statement, options)?;
+                if let Statement::Insert(insert) = &mut insert {
+                    insert.returning = Some(vec![SelectItem::Wildcard(
+                        WildcardAdditionalOptions::default(),
+                    )]);
+                }
+                Ok(insert)
+            }
+        }
+
+        let options = ConversionOptions::default();
+        let convert = |cypher: &str| {
+            let statement = parse_cypher(cypher).unwrap().remove(0);
+            convert_statement_with_converter(&statement, &options, &ReturningCreate)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(
+            convert("CREATE (n:Person {name: 'Alice'})"),
+            "INSERT INTO Person (name) VALUES ('Alice') RETURNING *"
+        );
+        assert_eq!(
+            convert("MATCH (n:Person) RETURN n.name"),
+            "SELECT n.name FROM Person AS n"
+        );
+    }
+
+    #[test]
+    fn test_custom_converter_converts_union_queries() {
+        struct DistinctMatch;
+
+        impl CypherConverter for DistinctMatch {
+            fn convert_match(
+                &self,
+                statement: &Statement,
+                options: &ConversionOptions,
+            ) -> Result<Statement, CypherConversionError> {
+                let mut query = DefaultConverter.convert_match(statement, options)?;
+                if let Statement::Query(query) = &mut query {
+                    if let SetExpr::Select(select) = query.body.as_mut() {
+                        select.distinct = Some(Distinct::Distinct);
+                    }
+                }
+                Ok(query)
+            }
+        }
+
+        let options = ConversionOptions::default();
+        let convert = |cypher: &str| {
+            let statement = parse_cypher(cypher).unwrap().remove(0);
+            convert_statement_with_converter(&statement, &options, &DistinctMatch)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(
+            convert(
+                "MATCH (n:Person) RETURN n.name AS name \
+                 UNION ALL MATCH (c:Company) RETURN c.name AS name"
+            ),
+            "SELECT DISTINCT n.name AS name FROM Person AS n \
+             UNION ALL SELECT DISTINCT c.name AS name FROM Company AS c"
+        );
+        assert_eq!(
+            convert("CALL { MATCH (n:Person) RETURN n.name AS name } RETURN name"),
+            "SELECT name FROM (SELECT DISTINCT n.name AS name FROM Person AS n) AS _q1"
+        );
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_validate_against_schema() {
+        let schema: HashMap<String, Vec<String>> = HashMap::from([
+            (
+                "Person".to_string(),
+                vec!["id".to_string(), "name".to_string(), "age".to_string()],
+            ),
+            (
+                "KNOWS".to_string(),
+                vec!["source_id".to_string(), "target_id".to_string()],
+            ),
+        ]);
+        let validate = |cypher: &str| {
+            validate_against_schema(
+                &convert_to_statement(cypher, &ConversionOptions::default()).unwrap(),
+                &schema,
+            )
+        };
+
+        assert_eq!(
+            validate("MATCH (a:Person)-[:KNOWS]->(b:Person) WHERE a.age > 30 RETURN b.name"),
+            Ok(())
+        );
+        assert_eq!(validate("CREATE (n:Person {name: 'Al', age: 3})"), Ok(()));
+        assert_eq!(
+            validate("MATCH (n:Person) RETURN n.nmae"),
+            Err("unknown column Person.nmae".to_string())
+        );
+        assert_eq!(
+            validate("MATCH (a:Person)-[:LIKES]->(b:Person) RETURN b"),
+            Err("unknown table LIKES".to_string())
+        );
+        assert_eq!(
+            validate("CREATE (n:Person {email: 'a@b'})"),
+            Err("unknown column Person.email".to_string())
+        );
+
+        // Tables are looked up with their schema
+        let schema: HashMap<String, Vec<String>> = HashMap::from([(
+            "analytics.persons".to_string(),
+            vec!["id".to_string(), "name".to_string()],
+        )]);
+        let options = ConversionOptions {
+            label_tables: HashMap::from([("Person".to_string(), "analytics.persons".to_string())]),
+            ..Default::default()
+        };
+        let validate = |cypher: &str| {
+            validate_against_schema(&convert_to_statement(cypher, &options).unwrap(), &schema)
+        };
+        assert_eq!(validate("MATCH (n:Person) RETURN n.name"), Ok(()));
+        assert_eq!(
+            validate("MATCH (n:Person) RETURN n.age"),
+            Err("unknown column analytics.persons.age".to_string())
+        );
+
+        // Every CTE is known, along with the columns read from it
+        let statement = Parser::parse_sql(
+            &GenericDialect {},
+            "WITH a AS (SELECT p.id FROM analytics.persons AS p), b AS (SELECT 1 AS x) \
+             SELECT a.id, b.x FROM a JOIN b ON true",
+        )
+        .unwrap()
+        .remove(0);
+        assert_eq!(validate_against_schema(&statement, &schema), Ok(()));
     }
-    
+
     #[test]
     fn test_cypher_create_to_sql() {
         let pattern = "(n:Person {name: 'Alice', age: 30})";
-        
+
         let result = cypher_create_to_sql(pattern);
         assert!(result.is_ok());
-        
+
         let sql_stmt = result.unwrap();
         let sql_str = sql_stmt.to_string();
-        
+
         println!("Generated INSERT SQL: {}", sql_str);
         assert!(sql_str.contains("INSERT"));
         assert!(sql_str.contains("Person"));
         assert!(sql_str.contains("name"));
         assert!(sql_str.contains("Alice"));
     }
-}
\ No newline at end of file
+}
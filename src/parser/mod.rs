@@ -287,6 +287,10 @@ enum ParserState {
     /// CREATE TABLE foo (abc BIGINT NOT NULL);
     /// ```
     ColumnDefinition,
+    /// The state when parsing a Cypher statement. This allows Cypher's
+    /// expression syntax, such as the pattern predicate in
+    /// `WHERE NOT (n)-[:BLOCKED]->(m)`, without changing how SQL parses.
+    Cypher,
 }
 
 /// A SQL Parser
@@ -598,7 +602,13 @@ impl<'a> Parser<'a> {
                 Keyword::UNCACHE => self.parse_uncache_table(),
                 Keyword::UPDATE => self.parse_update(),
                 Keyword::ALTER => self.parse_alter(),
-                Keyword::CALL => self.parse_call(),
+                Keyword::CALL => {
+                    if self.peek_token().token == Token::LBrace {
+                        self.parse_cypher_call()
+                    } else {
+                        self.parse_call()
+                    }
+                }
                 Keyword::COPY => self.parse_copy(),
                 Keyword::OPEN => {
                     self.prev_token();
@@ -2490,6 +2500,9 @@ impl<'a> Parser<'a> {
 
     /// Parse a SQL EXISTS expression e.g. `WHERE EXISTS(SELECT ...)`.
     pub fn parse_exists_expr(&mut self, negated: bool) -> Result<Expr, ParserError> {
+        if self.consume_token(&Token::LBrace) {
+            return self.parse_cypher_exists_expr(negated);
+        }
         self.expect_token(&Token::LParen)?;
         let exists_node = Expr::Exists {
             negated,
@@ -2816,6 +2829,13 @@ impl<'a> Parser<'a> {
     }
 
     pub fn parse_not(&mut self) -> Result<Expr, ParserError> {
+        if self.in_cypher_state() && self.peek_cypher_pattern() {
+            if let Some(predicate) =
+                self.maybe_parse(|parser| parser.parse_cypher_pattern_predicate(true))?
+            {
+                return Ok(predicate);
+            }
+        }
         match self.peek_token().token {
             Token::Word(w) => match w.keyword {
                 Keyword::EXISTS => {
@@ -3385,6 +3405,18 @@ impl<'a> Parser<'a> {
             return infix;
         }
 
+        if let Some(operator) = self.peek_cypher_string_operator() {
+            self.advance_token();
+            if operator != CypherStringOperator::Contains {
+                self.expect_keyword(Keyword::WITH)?;
+            }
+            return Ok(Expr::CypherStringMatch {
+                expr: Box::new(expr),
+                operator,
+                pattern: Box::new(self.parse_subexpr(self.dialect.prec_value(Precedence::Like))?),
+            });
+        }
+
         let dialect = self.dialect;
 
         self.advance_token();
@@ -3934,7 +3966,39 @@ impl<'a> Parser<'a> {
                 negated,
             });
         }
+        // A Cypher list, e.g. `id(n) IN [1, 2, 3]`
+        if self.in_cypher_state() && self.consume_token(&Token::LBracket) {
+            let list = self.parse_comma_separated0(Parser::parse_expr, Token::RBracket)?;
+            self.expect_token(&Token::RBracket)?;
+            return Ok(Expr::InList {
+                expr: Box::new(expr),
+                list,
+                negated,
+            });
+        }
+        // A Cypher list parameter, e.g. `n.id IN $ids`
+        if self.in_cypher_state() && matches!(self.peek_token().token, Token::Placeholder(_)) {
+            return Ok(Expr::CypherInParameter {
+                expr: Box::new(expr),
+                parameter: Box::new(Expr::Value(self.parse_value()?)),
+                negated,
+            });
+        }
         self.expect_token(&Token::LParen)?;
+        // A nested Cypher query, e.g. `n.id IN (MATCH (d:Dept) RETURN d.id)`
+        if self.in_cypher_state()
+            && self.peek_keyword(Keyword::MATCH)
+            && self.peek_nth_token_ref(1).token == Token::LParen
+        {
+            self.expect_keyword(Keyword::MATCH)?;
+            let query = self.parse_cypher_query()?;
+            self.expect_token(&Token::RParen)?;
+            return Ok(Expr::CypherInSubquery {
+                expr: Box::new(expr),
+                query: Box::new(query),
+                negated,
+            });
+        }
         let in_op = match self.maybe_parse(|p| p.parse_query())? {
             Some(subquery) => Expr::InSubquery {
                 expr: Box::new(expr),
@@ -3982,9 +4046,44 @@ impl<'a> Parser<'a> {
 
     /// Get the precedence of the next token
     pub fn get_next_precedence(&self) -> Result<u8, ParserError> {
+        if self.peek_cypher_string_operator().is_some() {
+            return Ok(self.dialect.prec_value(Precedence::Like));
+        }
         self.dialect.get_next_precedence_default(self)
     }
 
+    /// Returns the Cypher string predicate starting at the next token within
+    /// a Cypher statement, such as the `STARTS WITH` of
+    /// `n.name STARTS WITH 'A'`.
+    fn peek_cypher_string_operator(&self) -> Option<CypherStringOperator> {
+        if !self.in_cypher_state() {
+            return None;
+        }
+        match (&self.peek_token_ref().token, &self.peek_nth_token_ref(1).token) {
+            (Token::Word(w), Token::Word(next)) if next.keyword == Keyword::WITH => {
+                if w.keyword == Keyword::STARTS {
+                    Some(CypherStringOperator::StartsWith)
+                } else if w.value.eq_ignore_ascii_case("ENDS") {
+                    Some(CypherStringOperator::EndsWith)
+                } else {
+                    None
+                }
+            }
+            (Token::Word(w), next)
+                if w.keyword == Keyword::CONTAINS
+                    && match next {
+                        Token::Word(next) => next.keyword == Keyword::NoKeyword,
+                        next => {
+                            matches!(next, Token::SingleQuotedString(_) | Token::Placeholder(_))
+                        }
+                    } =>
+            {
+                Some(CypherStringOperator::Contains)
+            }
+            _ => None,
+        }
+    }
+
     /// Return the token at the given location, or EOF if the index is beyond
     /// the length of the current set of tokens.
     pub fn token_at(&self, index: usize) -> &TokenWithSpan {
@@ -10781,7 +10880,14 @@ impl<'a> Parser<'a> {
     /// Optionally parses an alias for a select list item
     fn maybe_parse_select_item_alias(&mut self) -> Result<Option<Ident>, ParserError> {
         fn validator(explicit: bool, kw: &Keyword, parser: &mut Parser) -> bool {
-            parser.dialect.is_select_item_alias(explicit, kw, parser)
+            // Cypher row counts, e.g. `RETURN n SKIP 10`
+            let skip = parser.in_cypher_state()
+                && *kw == Keyword::SKIP
+                && matches!(
+                    parser.peek_token_ref().token,
+                    Token::Number(..) | Token::Minus | Token::Placeholder(_)
+                );
+            (explicit || !skip) && parser.dialect.is_select_item_alias(explicit, kw, parser)
         }
         self.parse_optional_alias_inner(None, validator)
     }
@@ -15761,6 +15867,13 @@ impl<'a> Parser<'a> {
     }
 
     pub fn parse_function_args(&mut self) -> Result<FunctionArg, ParserError> {
+        // A Cypher pattern, as in `size((n)-[:KNOWS]->())`
+        if self.in_cypher_state() && self.peek_cypher_pattern() {
+            if let Some(pattern) = self.maybe_parse(|parser| parser.parse_cypher_pattern())? {
+                let pattern = Expr::CypherPattern(pattern);
+                return Ok(FunctionArg::Unnamed(FunctionArgExpr::Expr(pattern)));
+            }
+        }
         let arg = if self.dialect.supports_named_fn_args_with_expr_name() {
             self.maybe_parse(|p| {
                 let name = p.parse_expr()?;
@@ -17374,6 +17487,17 @@ impl<'a> Parser<'a> {
 
     /// Parse [Statement::Return]
     fn parse_return(&mut self) -> Result<Statement, ParserError> {
+        // A Cypher `RETURN 'hello' AS greeting, 42` without a `MATCH`
+        let index = self.index;
+        let cypher = self
+            .maybe_parse(|p| p.with_state(ParserState::Cypher, |p| p.parse_expr()))?
+            .is_some()
+            && (self.peek_token_ref().token == Token::Comma || self.peek_keyword(Keyword::AS));
+        self.index = index;
+        if cypher {
+            self.prev_token();
+            return self.parse_cypher_query();
+        }
         match self.maybe_parse(|p| p.parse_expr())? {
             Some(expr) => Ok(Statement::Return(ReturnStatement {
                 value: Some(ReturnStatementValue::Expr(expr)),
@@ -17581,6 +17705,10 @@ impl<'a> Parser<'a> {
         matches!(self.state, ColumnDefinition)
     }
 
+    pub(crate) fn in_cypher_state(&self) -> bool {
+        matches!(self.state, ParserState::Cypher)
+    }
+
     /// Parses options provided in key-value format.
     ///
     /// * `parenthesized` - true if the options are enclosed in parenthesis
@@ -17685,19 +17813,57 @@ impl<'a> Parser<'a> {
     }
 
     pub fn parse_cypher_query(&mut self) -> Result<Statement, ParserError> {
+        self.with_state(ParserState::Cypher, |parser| {
+            let mut query = parser.parse_cypher_single_query()?;
+            while parser.parse_keyword(Keyword::UNION) {
+                let all = parser.parse_keyword(Keyword::ALL);
+                if !parser.parse_keyword(Keyword::MATCH) && !parser.peek_keyword(Keyword::RETURN) {
+                    return parser.expected("MATCH or RETURN after UNION", parser.peek_token());
+                }
+                query = Statement::CypherUnion {
+                    left: Box::new(query),
+                    right: Box::new(parser.parse_cypher_single_query()?),
+                    all,
+                };
+            }
+            Ok(query)
+        })
+    }
+
+    /// Parse an expression with Cypher's syntax, such as a predicate given
+    /// on its own: `n.name STARTS WITH 'A'`.
+    pub fn parse_cypher_expr(&mut self) -> Result<Expr, ParserError> {
+        self.with_state(ParserState::Cypher, |parser| parser.parse_expr())
+    }
+
+    /// Parse a Cypher query up to any `UNION`, after the `MATCH` keyword.
+    fn parse_cypher_single_query(&mut self) -> Result<Statement, ParserError> {
         let mut pattern_parts = Vec::new();
 
         loop {
             let token = self.peek_token();
 
             if matches!(&token.token, Token::Word(w)
-                if w.keyword == Keyword::WHERE || w.keyword == Keyword::RETURN)
+                if w.keyword == Keyword::WHERE
+                    || w.keyword == Keyword::RETURN
+                    || w.keyword == Keyword::DETACH
+                    || w.keyword == Keyword::DELETE
+                    || w.keyword == Keyword::REMOVE
+                    || w.keyword == Keyword::SET
+                    || w.keyword == Keyword::MERGE)
             {
                 break;
             }
 
             if token.token == Token::EOF {
-                return self.expected("RETURN clause", token);
+                return self.expected("RETURN, DELETE, REMOVE, SET or MERGE clause", token);
+            }
+
+            // Each further `MATCH` adds its patterns to those already
+            // matched, as if they were separated by a comma.
+            if self.parse_keyword(Keyword::MATCH) {
+                pattern_parts.push(",".to_string());
+                continue;
             }
 
             let next = self.next_token();
@@ -17712,23 +17878,286 @@ impl<'a> Parser<'a> {
             None
         };
 
+        if self.parse_keyword(Keyword::REMOVE) {
+            let items = self.parse_comma_separated(Parser::parse_cypher_remove_item)?;
+            return Ok(Statement::CypherRemove {
+                pattern,
+                where_clause,
+                items,
+            });
+        }
+
+        if self.parse_keyword(Keyword::SET) {
+            let items = self.parse_comma_separated(Parser::parse_cypher_set_item)?;
+            return Ok(Statement::CypherSet {
+                pattern,
+                where_clause,
+                items,
+            });
+        }
+
+        if self.parse_keyword(Keyword::MERGE) {
+            let mut merge_parts = Vec::new();
+            while !matches!(self.peek_token().token, Token::EOF | Token::SemiColon) {
+                merge_parts.push(self.next_token().to_string());
+            }
+            return Ok(Statement::CypherMerge {
+                pattern,
+                where_clause,
+                merge: merge_parts.join(" "),
+            });
+        }
+
+        let detach = self.parse_keyword(Keyword::DETACH);
+        if detach || self.parse_keyword(Keyword::DELETE) {
+            if detach {
+                self.expect_keyword(Keyword::DELETE)?;
+            }
+            let variables = if self.consume_token(&Token::Mul) {
+                vec![]
+            } else {
+                self.parse_comma_separated(|p| p.parse_identifier())?
+            };
+            let return_items = if self.parse_keyword(Keyword::RETURN) {
+                self.parse_projection()?
+            } else {
+                vec![]
+            };
+            return Ok(Statement::CypherDelete {
+                pattern,
+                where_clause,
+                detach,
+                variables,
+                return_items,
+            });
+        }
+
         self.expect_keyword(Keyword::RETURN)?;
+        let distinct = self.parse_keyword(Keyword::DISTINCT);
         let return_items = self.parse_projection()?;
 
+        let order_by = if self.parse_keywords(&[Keyword::ORDER, Keyword::BY]) {
+            self.parse_comma_separated(Parser::parse_order_by_expr)?
+        } else {
+            vec![]
+        };
+        let skip = if self.parse_keyword(Keyword::SKIP) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        let limit = if self.parse_keyword(Keyword::LIMIT) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+
         Ok(Statement::CypherQuery {
             pattern,
             where_clause,
+            distinct,
             return_items,
+            order_by,
+            skip,
+            limit,
+        })
+    }
+
+    /// Parse an item of a Cypher `REMOVE` clause: `n.property` or `n:Label`.
+    fn parse_cypher_remove_item(&mut self) -> Result<CypherRemoveItem, ParserError> {
+        let variable = self.parse_identifier()?;
+        if self.consume_token(&Token::Period) {
+            Ok(CypherRemoveItem::Property {
+                variable,
+                property: self.parse_identifier()?,
+            })
+        } else {
+            self.expect_token(&Token::Colon)?;
+            Ok(CypherRemoveItem::Label {
+                variable,
+                label: self.parse_identifier()?,
+            })
+        }
+    }
+
+    /// Parse an item of a Cypher `SET` clause: `n.property = value` or
+    /// `n += {key: value, ...}`.
+    fn parse_cypher_set_item(&mut self) -> Result<CypherSetItem, ParserError> {
+        let variable = self.parse_identifier()?;
+        if self.consume_token(&Token::Period) {
+            let property = self.parse_identifier()?;
+            self.expect_token(&Token::Eq)?;
+            Ok(CypherSetItem::Property {
+                variable,
+                property,
+                value: self.parse_expr()?,
+            })
+        } else {
+            self.expect_token(&Token::Plus)?;
+            self.expect_token(&Token::Eq)?;
+            Ok(CypherSetItem::Merge {
+                variable,
+                properties: self.parse_expr()?,
+            })
+        }
+    }
+
+    /// Parse a Cypher `CALL { <query> } RETURN <items>`, after the `CALL`
+    /// keyword.
+    fn parse_cypher_call(&mut self) -> Result<Statement, ParserError> {
+        self.expect_token(&Token::LBrace)?;
+        if !self.parse_keyword(Keyword::MATCH) && !self.peek_keyword(Keyword::RETURN) {
+            return self.expected("MATCH or RETURN in CALL subquery", self.peek_token());
+        }
+        let subquery = self.parse_cypher_query()?;
+        self.expect_token(&Token::RBrace)?;
+
+        if !self.peek_keyword(Keyword::RETURN) {
+            return self.expected("RETURN after CALL subquery", self.peek_token());
+        }
+        let query =
+            self.with_state(ParserState::Cypher, |parser| parser.parse_cypher_single_query())?;
+        Ok(Statement::CypherCall {
+            subquery: Box::new(subquery),
+            query: Box::new(query),
         })
     }
 
+    /// Parse the body of a Cypher `EXISTS { MATCH <pattern> [ WHERE <expr> ] }`,
+    /// after the opening brace.
+    fn parse_cypher_exists_expr(&mut self, negated: bool) -> Result<Expr, ParserError> {
+        self.expect_keyword(Keyword::MATCH)?;
+
+        let mut pattern_parts = Vec::new();
+        let mut depth = 0usize;
+        loop {
+            let token = self.peek_token();
+            match &token.token {
+                Token::EOF => return self.expected("}", token),
+                Token::RBrace if depth == 0 => break,
+                Token::Word(w) if w.keyword == Keyword::WHERE && depth == 0 => break,
+                Token::LBrace => depth += 1,
+                Token::RBrace => depth -= 1,
+                _ => {}
+            }
+            let next = self.next_token();
+            pattern_parts.push(format!("{}", next));
+        }
+
+        let where_clause = if self.parse_keyword(Keyword::WHERE) {
+            Some(Box::new(self.parse_expr()?))
+        } else {
+            None
+        };
+        self.expect_token(&Token::RBrace)?;
+
+        Ok(Expr::CypherExists {
+            pattern: pattern_parts.join(" "),
+            where_clause,
+            negated,
+        })
+    }
+
+    /// Returns whether the upcoming tokens start a Cypher path pattern such
+    /// as `(n)-[:KNOWS]->(m)` rather than a parenthesized expression.
+    fn peek_cypher_pattern(&self) -> bool {
+        let mut depth = 0usize;
+        let mut n = 0;
+        loop {
+            match &self.peek_nth_token_ref(n).token {
+                Token::LParen => depth += 1,
+                Token::RParen if depth == 1 => break,
+                Token::RParen => depth = depth.saturating_sub(1),
+                Token::EOF => return false,
+                _ if depth == 0 => return false,
+                _ => {}
+            }
+            n += 1;
+        }
+        matches!(
+            (
+                &self.peek_nth_token_ref(n + 1).token,
+                &self.peek_nth_token_ref(n + 2).token,
+                &self.peek_nth_token_ref(n + 3).token,
+            ),
+            (Token::Minus, Token::LBracket, _) | (Token::Lt, Token::Minus, Token::LBracket)
+        )
+    }
+
+    /// Parse a Cypher pattern predicate such as `(n)-[:BLOCKED]->(m)`, which
+    /// holds when the pattern has a match, as an `EXISTS` over that pattern.
+    fn parse_cypher_pattern_predicate(&mut self, negated: bool) -> Result<Expr, ParserError> {
+        Ok(Expr::CypherExists {
+            pattern: self.parse_cypher_pattern()?,
+            where_clause: None,
+            negated,
+        })
+    }
+
+    /// Parse the text of a Cypher path pattern used within an expression,
+    /// such as `(n)-[:KNOWS]->()`: nodes joined by one or more
+    /// relationships.
+    fn parse_cypher_pattern(&mut self) -> Result<String, ParserError> {
+        let mut pattern_parts = Vec::new();
+        self.parse_cypher_pattern_group(Token::LParen, &mut pattern_parts)?;
+        loop {
+            // A relationship, `-[...]->`, `<-[...]-` or `-[...]-`
+            let incoming = self.consume_token(&Token::Lt);
+            if incoming {
+                pattern_parts.push(Token::Lt.to_string());
+            }
+            self.expect_token(&Token::Minus)?;
+            pattern_parts.push(Token::Minus.to_string());
+            self.parse_cypher_pattern_group(Token::LBracket, &mut pattern_parts)?;
+            let next = self.next_token();
+            match next.token {
+                Token::Minus => {}
+                Token::Arrow if !incoming => {}
+                _ => return self.expected("- or -> after relationship", next),
+            }
+            pattern_parts.push(next.to_string());
+
+            self.parse_cypher_pattern_group(Token::LParen, &mut pattern_parts)?;
+            if !matches!(self.peek_token_ref().token, Token::Minus | Token::Lt) {
+                break;
+            }
+        }
+        Ok(pattern_parts.join(" "))
+    }
+
+    /// Parse a bracketed part of a Cypher pattern, such as the node
+    /// `(n:Person {name: 'A'})`, adding its tokens to `pattern_parts`.
+    fn parse_cypher_pattern_group(
+        &mut self,
+        open: Token,
+        pattern_parts: &mut Vec<String>,
+    ) -> Result<(), ParserError> {
+        self.expect_token(&open)?;
+        pattern_parts.push(open.to_string());
+        let mut depth = 1usize;
+        while depth > 0 {
+            let token = self.next_token();
+            match &token.token {
+                Token::LParen | Token::LBracket | Token::LBrace => depth += 1,
+                Token::RParen | Token::RBracket | Token::RBrace => depth -= 1,
+                Token::EOF => return self.expected("end of pattern", token),
+                _ => {}
+            }
+            pattern_parts.push(token.to_string());
+        }
+        Ok(())
+    }
+
     pub fn parse_cypher_create(&mut self) -> Result<Statement, ParserError> {
         let mut pattern_parts = Vec::new();
 
         loop {
             let token = self.peek_token();
 
-            if token.token == Token::EOF || token.token == Token::SemiColon {
+            if token.token == Token::EOF
+                || token.token == Token::SemiColon
+                || matches!(&token.token, Token::Word(w) if w.keyword == Keyword::WITH)
+            {
                 break;
             }
 
@@ -17737,7 +18166,24 @@ impl<'a> Parser<'a> {
         }
 
         let pattern = pattern_parts.join(" ");
-        Ok(Statement::CypherCreate { pattern })
+
+        let (with, query) = if self.parse_keyword(Keyword::WITH) {
+            let with = if self.consume_token(&Token::Mul) {
+                vec![]
+            } else {
+                self.parse_comma_separated(|p| p.parse_identifier())?
+            };
+            self.expect_keyword(Keyword::MATCH)?;
+            (with, Some(Box::new(self.parse_cypher_query()?)))
+        } else {
+            (vec![], None)
+        };
+
+        Ok(Statement::CypherCreate {
+            pattern,
+            with,
+            query,
+        })
     }
 }
 
@@ -17789,7 +18235,7 @@ mod tests {
         assert_eq!(statements.len(), 1);
         
         match &statements[0] {
-            Statement::CypherQuery { pattern, where_clause, return_items } => {
+            Statement::CypherQuery { pattern, where_clause, return_items, .. } => {
                 assert!(pattern.contains("Person"), "Pattern should contain 'Person': {}", pattern);
                 assert!(where_clause.is_none(), "No WHERE clause expected");
                 assert_eq!(return_items.len(), 1, "Should have 1 return item");
@@ -17808,7 +18254,7 @@ mod tests {
         let statements = result.unwrap();
         
         match &statements[0] {
-            Statement::CypherQuery { pattern, where_clause, return_items } => {
+            Statement::CypherQuery { pattern, where_clause, return_items, .. } => {
                 assert!(pattern.contains("Person"));
                 assert!(where_clause.is_some(), "WHERE clause expected");
                 assert_eq!(return_items.len(), 2, "Should have 2 return items");
@@ -17817,6 +18263,427 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_cypher_lowercase_keywords() {
+        let sql = "match (n:Person) where n.age > 25 return n.name as x";
+        let dialect = GenericDialect {};
+        let statements = Parser::parse_sql(&dialect, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherQuery { where_clause, return_items, .. } => {
+                assert!(where_clause.is_some(), "WHERE clause expected");
+                assert_eq!(
+                    return_items[0],
+                    SelectItem::ExprWithAlias {
+                        expr: Expr::CompoundIdentifier(vec![Ident::new("n"), Ident::new("name")]),
+                        alias: Ident::new("x"),
+                    }
+                );
+            }
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_cypher_exists_subquery() {
+        let sql = "MATCH (n:Person) WHERE NOT EXISTS { MATCH (n)-[:KNOWS]->({name: 'Bob'}) WHERE n.age > 3 } RETURN n";
+        let dialect = GenericDialect {};
+        let statements = Parser::parse_sql(&dialect, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherQuery { where_clause, .. } => match where_clause {
+                Some(Expr::CypherExists { pattern, where_clause, negated }) => {
+                    assert_eq!(pattern, "( n ) - [ : KNOWS ] -> ( { name : 'Bob' } )");
+                    assert!(where_clause.is_some());
+                    assert!(*negated);
+                }
+                other => panic!("Expected CypherExists, got: {:?}", other),
+            },
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+        assert_eq!(
+            statements[0].to_string(),
+            "MATCH ( n : Person ) WHERE NOT EXISTS { MATCH ( n ) - [ : KNOWS ] -> ( { name : 'Bob' } ) WHERE n.age > 3 } RETURN n"
+        );
+    }
+
+    #[test]
+    fn test_parse_cypher_negated_pattern_predicate() {
+        let sql = "MATCH (n:Person), (m:Person) WHERE NOT (n)-[:BLOCKED]->(m) AND m.age > 3 RETURN m";
+        let dialect = GenericDialect {};
+        let statements = Parser::parse_sql(&dialect, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherQuery { where_clause, .. } => match where_clause {
+                Some(Expr::BinaryOp { left, .. }) => match left.as_ref() {
+                    Expr::CypherExists { pattern, where_clause, negated } => {
+                        assert_eq!(pattern, "( n ) - [ : BLOCKED ] -> ( m )");
+                        assert!(where_clause.is_none());
+                        assert!(*negated);
+                    }
+                    other => panic!("Expected CypherExists, got: {:?}", other),
+                },
+                other => panic!("Expected BinaryOp, got: {:?}", other),
+            },
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+
+        let sql = "MATCH (n:Person) WHERE NOT (n.age) - 1 > 3 RETURN n";
+        match &Parser::parse_sql(&dialect, sql).unwrap()[0] {
+            Statement::CypherQuery { where_clause, .. } => assert!(matches!(
+                where_clause,
+                Some(Expr::UnaryOp { op: UnaryOperator::Not, .. })
+            )),
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+
+        // Outside of Cypher, the parentheses group an operand.
+        let sql = "SELECT * FROM t WHERE NOT (a) - [1] = b";
+        match &Parser::parse_sql(&dialect, sql).unwrap()[0] {
+            Statement::Query(query) => match query.body.as_ref() {
+                SetExpr::Select(select) => match &select.selection {
+                    Some(Expr::UnaryOp {
+                        op: UnaryOperator::Not,
+                        expr,
+                    }) => assert!(matches!(
+                        expr.as_ref(),
+                        Expr::BinaryOp {
+                            op: BinaryOperator::Eq,
+                            ..
+                        }
+                    )),
+                    other => panic!("Expected NOT, got: {:?}", other),
+                },
+                other => panic!("Expected Select, got: {:?}", other),
+            },
+            other => panic!("Expected Query, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_cypher_return_without_match() {
+        let sql = "RETURN 'hello' AS greeting, 42 AS answer";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        match &statements[0] {
+            Statement::CypherQuery { pattern, return_items, .. } => {
+                assert!(pattern.is_empty());
+                assert_eq!(return_items.len(), 2);
+            }
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+
+        assert!(matches!(
+            Parser::parse_sql(&GenericDialect {}, "RETURN 42").unwrap()[0],
+            Statement::Return(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_cypher_in_match_subquery() {
+        let sql = "MATCH ( n : Person ) WHERE n.dept_id IN (MATCH ( d : Dept ) RETURN d.id) RETURN n.name";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        match &statements[0] {
+            Statement::CypherQuery { where_clause, .. } => match where_clause {
+                Some(Expr::CypherInSubquery { query, negated, .. }) => {
+                    assert!(matches!(query.as_ref(), Statement::CypherQuery { .. }));
+                    assert!(!*negated);
+                }
+                other => panic!("Expected CypherInSubquery, got: {:?}", other),
+            },
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+
+        // In SQL, `match` may name a column of an IN list.
+        TestedDialects::new(vec![
+            Box::new(GenericDialect {}),
+            Box::new(crate::dialect::PostgreSqlDialect {}),
+        ])
+        .verified_stmt("SELECT * FROM t WHERE a IN (match, b)");
+    }
+
+    #[test]
+    fn test_parse_cypher_union() {
+        let sql = "MATCH ( n : Person ) RETURN n.name AS name UNION MATCH ( c : Company ) RETURN c.name AS name UNION ALL RETURN 'x' AS name";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        match &statements[0] {
+            Statement::CypherUnion { left, right, all } => {
+                assert!(*all);
+                assert!(matches!(left.as_ref(), Statement::CypherUnion { all: false, .. }));
+                assert!(matches!(right.as_ref(), Statement::CypherQuery { .. }));
+            }
+            other => panic!("Expected CypherUnion, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+    }
+
+    #[test]
+    fn test_parse_cypher_order_by() {
+        let sql = "MATCH ( n : Person ) RETURN n.a ORDER BY n.a ASC, n.b DESC, n.c";
+        let dialect = GenericDialect {};
+        let statements = Parser::parse_sql(&dialect, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherQuery { order_by, .. } => assert_eq!(order_by.len(), 3),
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+    }
+
+    #[test]
+    fn test_parse_cypher_detach_delete() {
+        let sql = "MATCH ( n : Person ) WHERE n.id = 1 DETACH DELETE n";
+        let dialect = GenericDialect {};
+        let statements = Parser::parse_sql(&dialect, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherDelete {
+                detach, variables, ..
+            } => {
+                assert!(*detach);
+                assert_eq!(variables, &vec![Ident::new("n")]);
+            }
+            other => panic!("Expected CypherDelete, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+    }
+
+    #[test]
+    fn test_parse_cypher_delete_all() {
+        let sql = "MATCH ( : Person { id : 1 } ) DELETE *";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherDelete { variables, .. } => assert!(variables.is_empty()),
+            other => panic!("Expected CypherDelete, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+    }
+
+    #[test]
+    fn test_parse_cypher_call_subquery() {
+        let sql = "CALL { MATCH ( n : Person ) RETURN n } RETURN n.name";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherCall { subquery, query } => {
+                assert!(matches!(**subquery, Statement::CypherQuery { .. }));
+                assert!(matches!(**query, Statement::CypherQuery { ref pattern, .. } if pattern.is_empty()));
+            }
+            other => panic!("Expected CypherCall, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+    }
+
+    #[test]
+    fn test_parse_cypher_remove() {
+        let sql = "MATCH ( n : Person ) WHERE n.id = 1 REMOVE n.temp, n:Admin";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherRemove { items, .. } => assert_eq!(
+                items,
+                &vec![
+                    CypherRemoveItem::Property {
+                        variable: Ident::new("n"),
+                        property: Ident::new("temp"),
+                    },
+                    CypherRemoveItem::Label {
+                        variable: Ident::new("n"),
+                        label: Ident::new("Admin"),
+                    },
+                ]
+            ),
+            other => panic!("Expected CypherRemove, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+    }
+
+    #[test]
+    fn test_parse_cypher_set() {
+        let sql = "MATCH ( n : Person ) WHERE n.id = 1 SET n += {a: 1, b: 'x'}, n.c = 2";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherSet { items, .. } => {
+                assert_eq!(items.len(), 2);
+                assert!(matches!(
+                    &items[0],
+                    CypherSetItem::Merge {
+                        properties: Expr::Dictionary(fields),
+                        ..
+                    } if fields.len() == 2
+                ));
+                assert!(matches!(
+                    &items[1],
+                    CypherSetItem::Property { property, value, .. }
+                        if property.value == "c" && value.to_string() == "2"
+                ));
+            }
+            other => panic!("Expected CypherSet, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+    }
+
+    #[test]
+    fn test_parse_cypher_skip_limit() {
+        let sql = "MATCH ( n : Person ) RETURN n ORDER BY n.name SKIP 10 LIMIT 5";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherQuery {
+                skip: Some(skip),
+                limit: Some(limit),
+                ..
+            } => {
+                assert_eq!(skip.to_string(), "10");
+                assert_eq!(limit.to_string(), "5");
+            }
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+
+        // SKIP directly after a return item is not its alias.
+        let sql = "MATCH ( n : Person ) RETURN n SKIP 10 LIMIT 5";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        assert_eq!(statements[0].to_string(), sql);
+    }
+
+    #[test]
+    fn test_parse_cypher_multiple_match() {
+        let sql = "MATCH (a:Person) MATCH (b:Company) RETURN a, b";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherQuery { pattern, .. } => {
+                assert_eq!(pattern, "( a : Person ) , ( b : Company )")
+            }
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_cypher_in_list() {
+        let sql = "MATCH ( n : Person ) WHERE n.id IN (1, 2) RETURN n";
+        let statements =
+            Parser::parse_sql(&GenericDialect {}, "MATCH (n:Person) WHERE n.id IN [1, 2] RETURN n")
+                .unwrap();
+        match &statements[0] {
+            Statement::CypherQuery { where_clause, .. } => {
+                assert!(matches!(where_clause, Some(Expr::InList { list, .. }) if list.len() == 2))
+            }
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+
+        // SQL has no bracketed IN list.
+        assert!(Parser::parse_sql(&GenericDialect {}, "SELECT * FROM t WHERE a IN [1, 2]").is_err());
+    }
+
+    #[test]
+    fn test_parse_cypher_in_parameter() {
+        let sql = "MATCH ( n : Person ) WHERE n.id NOT IN $ids AND n.age > 18 RETURN n";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherQuery {
+                where_clause: Some(Expr::BinaryOp { left, .. }),
+                ..
+            } => assert!(matches!(
+                left.as_ref(),
+                Expr::CypherInParameter { negated: true, .. }
+            )),
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+
+        // SQL takes the IN list in parentheses.
+        assert!(Parser::parse_sql(&GenericDialect {}, "SELECT * FROM t WHERE a IN $1").is_err());
+    }
+
+    #[test]
+    fn test_parse_cypher_merge() {
+        let sql = "MATCH ( a : Person ) , ( b : Person ) WHERE a.id = 1 MERGE ( a ) - [ : KNOWS ] -> ( b )";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherMerge {
+                where_clause: Some(_),
+                merge,
+                ..
+            } => assert_eq!(merge, "( a ) - [ : KNOWS ] -> ( b )"),
+            other => panic!("Expected CypherMerge, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+    }
+
+    #[test]
+    fn test_parse_cypher_pattern_argument() {
+        let sql = "MATCH ( n : Person ) WHERE size(( n ) - [ : KNOWS ] -> ( )) > 3 RETURN n";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherQuery {
+                where_clause: Some(Expr::BinaryOp { left, .. }),
+                ..
+            } => match left.as_ref() {
+                Expr::Function(Function {
+                    args: FunctionArguments::List(list),
+                    ..
+                }) => assert_eq!(
+                    list.args,
+                    vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(
+                        Expr::CypherPattern("( n ) - [ : KNOWS ] -> ( )".to_string())
+                    ))]
+                ),
+                other => panic!("Expected Function, got: {:?}", other),
+            },
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+
+        // Without a full pattern, parentheses group an operand.
+        let sql = "MATCH ( n : Person ) RETURN f((n.a) - [1])";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        assert_eq!(statements[0].to_string(), sql);
+        TestedDialects::new(vec![
+            Box::new(GenericDialect {}),
+            Box::new(crate::dialect::PostgreSqlDialect {}),
+        ])
+        .verified_stmt("SELECT f((a) - [1])");
+    }
+
+    #[test]
+    fn test_parse_cypher_string_predicates() {
+        let sql = "MATCH ( n : Person ) WHERE n.name STARTS WITH 'A' OR n.name ENDS WITH 'z' \
+                   OR n.bio CONTAINS $word RETURN n";
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+
+        match &statements[0] {
+            Statement::CypherQuery {
+                where_clause: Some(Expr::BinaryOp { right, .. }),
+                ..
+            } => assert!(matches!(
+                **right,
+                Expr::CypherStringMatch {
+                    operator: CypherStringOperator::Contains,
+                    ..
+                }
+            )),
+            other => panic!("Expected CypherQuery, got: {:?}", other),
+        }
+        assert_eq!(statements[0].to_string(), sql);
+
+        // SQL has no such operators.
+        for sql in [
+            "SELECT a CONTAINS 'x'",
+            "SELECT * FROM t WHERE a STARTS WITH 'x'",
+        ] {
+            assert!(Parser::parse_sql(&GenericDialect {}, sql).is_err());
+        }
+    }
+
     #[test]
     fn test_cypher_round_trip() {
         let sql = "MATCH ( n : Person ) RETURN n.name";
@@ -17837,7 +18704,7 @@ mod tests {
         
         assert_eq!(statements.len(), 1);
         match &statements[0] {
-            Statement::CypherCreate { pattern } => {
+            Statement::CypherCreate { pattern, .. } => {
                 // Debug: print what we actually got
                 println!("Pattern captured: '{}'", pattern);
                 assert!(pattern.contains("Person"), "Pattern should contain 'Person', got: {}", pattern);
@@ -17848,6 +18715,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_cypher_create_with_match() {
+        let sql = "CREATE (n:Person {name: 'Alice'}) WITH n MATCH (n)-[:KNOWS]->(m:Person) RETURN m";
+        let dialect = GenericDialect {};
+        let statements = Parser::parse_sql(&dialect, sql).unwrap();
+
+        assert_eq!(statements.len(), 1);
+        match &statements[0] {
+            Statement::CypherCreate { pattern, with, query } => {
+                assert!(!pattern.contains("WITH"), "Pattern should stop at WITH, got: {}", pattern);
+                assert_eq!(with, &vec![Ident::new("n")]);
+                assert!(matches!(query.as_deref(), Some(Statement::CypherQuery { .. })));
+            }
+            _ => panic!("Expected CypherCreate statement, got: {:?}", statements[0]),
+        }
+        assert_eq!(
+            statements[0].to_string(),
+            "CREATE ( n : Person { name : 'Alice' } ) WITH n MATCH ( n ) - [ : KNOWS ] -> ( m : Person ) RETURN m"
+        );
+    }
+
     #[test]
     fn test_parse_cypher_create_no_properties() {
         let sql = "CREATE (n:Person)";
@@ -17856,7 +18744,7 @@ mod tests {
         
         assert_eq!(statements.len(), 1);
         match &statements[0] {
-            Statement::CypherCreate { pattern } => {
+            Statement::CypherCreate { pattern, .. } => {
                 // Debug: print what we actually got
                 println!("Pattern captured: '{}'", pattern);
                 assert!(pattern.contains("Person"), "Pattern should contain 'Person', got: {}", pattern);